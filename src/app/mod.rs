@@ -1,27 +1,66 @@
-mod world;
+mod ticker;
+mod view;
+
+use std::mem;
 
 use eframe::{
-    egui::{self, Button, Painter, Sense, Slider, SliderOrientation},
+    egui::{self, Button, ComboBox, DragValue, Painter, Sense, Slider, SliderOrientation},
     epaint::{vec2, Pos2},
     epi::{self},
 };
 
+use crate::world::{
+    boundary::Boundary,
+    cell::{Cell, CellType, Emission},
+    timeline::Timeline,
+    World,
+};
 use egui::{Color32, Rounding, Shape};
-use world::cell::CellType;
-use world::World;
+use ticker::Ticker;
+use view::View;
+
+/// Most generations kept for rewinding.
+const HISTORY_LENGTH: usize = 300;
+/// Memory the rewind history may take up. Large worlds keep fewer
+/// generations.
+const HISTORY_BYTES: usize = 32 * 1024 * 1024;
+
+/// How many generations of a `width` × `height` world fit in `HISTORY_BYTES`.
+fn history_length(width: usize, height: usize) -> usize {
+    let snapshot = width * height * mem::size_of::<Cell>();
+    (HISTORY_BYTES / snapshot.max(1)).clamp(1, HISTORY_LENGTH)
+}
 
 pub struct App {
     running: bool,
     world: World,
+    view: View,
+    ticker: Ticker,
+    timeline: Timeline,
+    selected_cell_type: CellType,
+    /// Settings given to newly painted emitters.
+    emission: Emission,
+    /// Emitter whose settings the menu edits.
+    inspected: Option<(i32, i32)>,
+    width: usize,
+    height: usize,
 }
 
 impl Default for App {
     fn default() -> Self {
-        let block_size = 1.0;
-        let num_of_blocks = (100.0 / block_size) as usize;
+        let width = 100;
+        let height = 100;
         Self {
             running: false,
-            world: World::new(num_of_blocks),
+            world: World::new(width, height),
+            view: View::default(),
+            ticker: Ticker::default(),
+            timeline: Timeline::new(history_length(width, height)),
+            selected_cell_type: CellType::WATER,
+            emission: Emission::default(),
+            inspected: None,
+            width,
+            height,
         }
     }
 }
@@ -41,28 +80,104 @@ impl epi::App for App {
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &epi::Frame) {
-        let Self { world, running } = self;
+        let Self {
+            world,
+            running,
+            view,
+            ticker,
+            timeline,
+            selected_cell_type,
+            emission,
+            inspected,
+            width,
+            height,
+        } = self;
 
         ctx.request_repaint();
 
         egui::Window::new("Menu").resizable(false).show(ctx, |ui| {
             if ui
                 .add(
-                    Slider::new(&mut world.block_size, 5.0..=30.0)
+                    Slider::new(&mut view.block_size, 5.0..=30.0)
                         .step_by(1.0)
                         .orientation(SliderOrientation::Horizontal)
                         .text("Block size"),
                 )
                 .changed()
             {
-                world.update_pos();
+                view.update_pos();
             }
 
+            ui.horizontal(|ui| {
+                ui.add(
+                    DragValue::new(width)
+                        .clamp_range(3..=1000)
+                        .prefix("Width: "),
+                );
+                ui.add(
+                    DragValue::new(height)
+                        .clamp_range(3..=1000)
+                        .prefix("Height: "),
+                );
+
+                if ui.add_enabled(!*running, Button::new("Resize")).clicked() {
+                    let threshold = world.threshold;
+                    let ambient_temperature = world.ambient_temperature;
+                    let boundaries = world.boundaries;
+                    *world = World::with_seed(*width, *height, world.seed());
+                    world.threshold = threshold;
+                    world.ambient_temperature = ambient_temperature;
+                    world.boundaries = boundaries;
+                    world.add_border();
+                    *timeline = Timeline::new(history_length(*width, *height));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let mut seed = world.seed();
+                if ui
+                    .add_enabled(!*running, DragValue::new(&mut seed).prefix("Seed: "))
+                    .changed()
+                {
+                    world.set_seed(seed);
+                }
+
+                if ui
+                    .add_enabled(!*running, Button::new("Random seed"))
+                    .clicked()
+                {
+                    world.set_seed(rand::random::<u32>() as u64);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let boundaries = &mut world.boundaries;
+                for (label, edge) in [
+                    ("Top", &mut boundaries.top),
+                    ("Bottom", &mut boundaries.bottom),
+                    ("Left", &mut boundaries.left),
+                    ("Right", &mut boundaries.right),
+                ] {
+                    ComboBox::from_label(label)
+                        .selected_text(edge.name())
+                        .show_ui(ui, |ui| {
+                            for boundary in Boundary::ALL {
+                                ui.selectable_value(edge, boundary, boundary.name());
+                            }
+                        });
+                }
+            });
+
             ui.separator();
 
             ui.horizontal(|ui| {
                 if ui.add(Button::new("Toggle")).clicked() {
                     *running = !*running;
+                    ticker.reset();
+                }
+
+                if ui.add_enabled(!*running, Button::new("Step")).clicked() {
+                    step(world, timeline);
                 }
 
                 if ui
@@ -70,58 +185,89 @@ impl epi::App for App {
                     .clicked()
                 {
                     world.rand_generate();
+                    timeline.clear();
                 }
 
                 if ui.add_enabled(!*running, Button::new("Clear")).clicked() {
                     world.clear();
+                    timeline.clear();
                 }
                 if ui.add_enabled(!*running, Button::new("Smooth")).clicked() {
                     world.smooth();
                 }
 
                 if ui.add(Button::new("Toggle shift")).clicked() {
-                    world.toggle_shift();
+                    view.toggle_shift();
+                }
+                if ui.add(Button::new("Toggle heat")).clicked() {
+                    view.toggle_heat();
                 }
             });
 
-            if ui
-                .add(
-                    Slider::new(&mut world.fps, 1..=360)
-                        .step_by(1.0)
-                        .orientation(SliderOrientation::Horizontal)
-                        .text("FPS"),
-                )
-                .changed()
-            {
-                world.update_speed();
-            }
+            ui.horizontal(|ui| {
+                let can_scrub = !*running && !timeline.is_empty();
+
+                if ui.add_enabled(can_scrub, Button::new("<")).clicked() {
+                    timeline.back(world);
+                }
+
+                let mut cursor = timeline.cursor();
+                if ui
+                    .add_enabled(
+                        can_scrub,
+                        Slider::new(&mut cursor, 0..=timeline.len().saturating_sub(1))
+                            .orientation(SliderOrientation::Horizontal)
+                            .text("History"),
+                    )
+                    .changed()
+                {
+                    timeline.seek(cursor, world);
+                }
+
+                if ui.add_enabled(can_scrub, Button::new(">")).clicked() {
+                    timeline.forward(world);
+                }
+            });
+
+            ui.add(
+                Slider::new(&mut ticker.fps, 1.0..=1000.0)
+                    .logarithmic(true)
+                    .orientation(SliderOrientation::Horizontal)
+                    .text("FPS"),
+            );
 
             if ui
                 .add(
-                    Slider::new(&mut world.shift.x, 0.0..=100.0)
+                    Slider::new(&mut view.shift.x, 0.0..=world.width() as f32)
                         .step_by(1.0)
                         .orientation(SliderOrientation::Horizontal)
                         .text("Shift x"),
                 )
                 .changed()
             {
-                world.update_pos();
+                view.update_pos();
             }
 
             if ui
                 .add(
-                    Slider::new(&mut world.shift.y, 0.0..=100.0)
+                    Slider::new(&mut view.shift.y, 0.0..=world.height() as f32)
                         .step_by(1.0)
                         .orientation(SliderOrientation::Horizontal)
                         .text("Shift y"),
                 )
                 .changed()
             {
-                world.update_pos();
+                view.update_pos();
             }
 
             ui.separator();
 
+            ui.add(
+                Slider::new(&mut world.ambient_temperature, -50.0..=150.0)
+                    .orientation(SliderOrientation::Horizontal)
+                    .text("Ambient °C"),
+            );
+
             ui.add(
                 Slider::new(&mut world.threshold, 0.0..=1.0)
                     .step_by(0.01)
@@ -131,67 +277,55 @@ impl epi::App for App {
 
             ui.separator();
 
-            ui.horizontal(|ui| {
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::Water),
-                        Button::new("Water"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::Water;
-                }
-
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::Dirt),
-                        Button::new("Dirt"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::Dirt;
-                }
-
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::Sand),
-                        Button::new("Sand"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::Sand;
-                }
-
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::Wood),
-                        Button::new("Wood"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::Wood;
-                }
-
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::FireNormal),
-                        Button::new("Fire"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::FireNormal;
-                }
-
-                if ui
-                    .add_enabled(
-                        world.selected_cell_type.ne(&CellType::None),
-                        Button::new("Empty"),
-                    )
-                    .clicked()
-                {
-                    world.selected_cell_type = CellType::None;
+            ui.horizontal_wrapped(|ui| {
+                for (cell_type, props) in world.registry().iter() {
+                    if props.paintable
+                        && ui
+                            .add_enabled(*selected_cell_type != cell_type, Button::new(props.name))
+                            .clicked()
+                    {
+                        *selected_cell_type = cell_type;
+                    }
                 }
             });
+
+            let emittable: Vec<(CellType, &str)> = world
+                .registry()
+                .iter()
+                .filter(|&(cell_type, props)| {
+                    props.paintable && cell_type != CellType::EMITTER && cell_type != CellType::SINK
+                })
+                .map(|(cell_type, props)| (cell_type, props.name))
+                .collect();
+            let selected = inspected.and_then(|(y, x)| world.emission_mut(y, x));
+            if selected.is_some() || *selected_cell_type == CellType::EMITTER {
+                ui.separator();
+
+                let label = if selected.is_some() {
+                    "Selected emitter"
+                } else {
+                    "New emitters"
+                };
+                let emission = selected.unwrap_or(&mut *emission);
+                let name = emittable
+                    .iter()
+                    .find(|(cell_type, _)| *cell_type == emission.cell_type)
+                    .map_or("", |(_, name)| name);
+                ui.horizontal(|ui| {
+                    ComboBox::from_label(label)
+                        .selected_text(name)
+                        .show_ui(ui, |ui| {
+                            for &(cell_type, name) in &emittable {
+                                ui.selectable_value(&mut emission.cell_type, cell_type, name);
+                            }
+                        });
+                    ui.add(
+                        Slider::new(&mut emission.rate, 0.0..=1.0)
+                            .orientation(SliderOrientation::Horizontal)
+                            .text("Rate"),
+                    );
+                });
+            }
         });
 
         egui::Window::new("Display")
@@ -220,8 +354,8 @@ impl epi::App for App {
                     )
                     .dragged()
                 {
-                    world.transform_cell(
-                        ui.interact(
+                    if let Some(pos) = ui
+                        .interact(
                             ui.clip_rect(),
                             ui.id(),
                             Sense {
@@ -230,20 +364,46 @@ impl epi::App for App {
                                 focusable: false,
                             },
                         )
-                        .hover_pos(),
-                        rect,
-                    )
+                        .hover_pos()
+                    {
+                        let (y, x) = view.block_pos(pos, rect);
+                        if *selected_cell_type == CellType::EMITTER {
+                            // Painting over an emitter selects it instead
+                            if world.emission_mut(y, x).is_none() {
+                                world.transform_cell(y, x, CellType::EMITTER);
+                                if let Some(new) = world.emission_mut(y, x) {
+                                    *new = *emission;
+                                }
+                            }
+                            *inspected = Some((y, x));
+                        } else {
+                            world.transform_cell(y, x, *selected_cell_type);
+                        }
+                    }
                 };
 
                 if *running {
-                    world.update();
+                    for _ in 0..ticker.ticks() {
+                        step(world, timeline);
+                    }
                 }
 
                 shapes.push(Shape::rect_filled(rect, Rounding::none(), Color32::WHITE));
 
-                world.gen_shapes(&mut shapes, rect);
+                view.gen_shapes(world, &mut shapes, rect);
 
                 painter.extend(shapes);
             });
     }
 }
+
+/// Advances `world` by one generation and records the result, so it can be
+/// rewound later.
+fn step(world: &mut World, timeline: &mut Timeline) {
+    if timeline.is_empty() {
+        timeline.record(world);
+    }
+
+    world.step();
+    timeline.record(world);
+}
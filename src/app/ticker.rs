@@ -0,0 +1,50 @@
+use instant::Instant;
+
+/// Never run more than this many steps in a single frame, so a slow frame
+/// cannot snowball into an ever growing backlog.
+const MAX_STEPS_PER_FRAME: u32 = 64;
+
+/// Turns wall-clock time into a number of fixed-size simulation steps.
+pub struct Ticker {
+    pub fps: f32,
+    accumulator: f32,
+    last_frame_time: Instant,
+}
+
+impl Default for Ticker {
+    fn default() -> Self {
+        Self {
+            fps: 60.0,
+            accumulator: 0.0,
+            last_frame_time: Instant::now(),
+        }
+    }
+}
+
+impl Ticker {
+    /// Returns how many steps should run this frame (zero or more).
+    pub fn ticks(&mut self) -> u32 {
+        let now = Instant::now();
+        self.accumulator += now.duration_since(self.last_frame_time).as_secs_f32();
+        self.last_frame_time = now;
+
+        let step = 1.0 / self.fps;
+        let mut ticks = 0;
+        while self.accumulator >= step && ticks < MAX_STEPS_PER_FRAME {
+            self.accumulator -= step;
+            ticks += 1;
+        }
+
+        if ticks == MAX_STEPS_PER_FRAME {
+            self.accumulator = 0.0;
+        }
+
+        ticks
+    }
+
+    /// Forgets the time spent paused, so resuming does not burst steps.
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+        self.last_frame_time = Instant::now();
+    }
+}
@@ -0,0 +1,182 @@
+use egui::{pos2, vec2, Color32, Pos2, Rect, Rounding, Shape, Vec2};
+
+use crate::world::{
+    cell::{Cell, CellType, AMBIENT_TEMPERATURE},
+    material::State,
+    World, MAX_MASS,
+};
+
+const MIN_DRAW: f32 = 0.1;
+/// Temperature drawn at full brightness by the heat view.
+const MAX_DRAW_TEMPERATURE: f32 = 1000.0;
+/// How strongly each cell's worth of sediment tints a liquid.
+const MUD_TINT: f32 = 0.5;
+
+/// Draws a `World` into egui shapes and maps pointer positions back to cells.
+pub struct View {
+    pub block_size: f32,
+    pub pos_shift: Vec2,
+    pub shift: Vec2,
+    pub use_shift: bool,
+    /// Draws temperature instead of materials.
+    pub show_heat: bool,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            block_size: 5.0,
+            pos_shift: vec2(0.0, 0.0),
+            shift: vec2(0.0, 0.0),
+            use_shift: false,
+            show_heat: false,
+        }
+    }
+}
+
+impl View {
+    pub fn color(&self, world: &World, cell: &Cell) -> Color32 {
+        if self.show_heat {
+            return View::heat_color(cell.temperature);
+        }
+
+        let [r, g, b] = world.registry().get(cell.cell_type).color;
+        if cell.sediment <= 0.0 {
+            return Color32::from_rgb(r, g, b);
+        }
+
+        // Muddy water, tinted by the sand it carries
+        let share = (cell.sediment * MUD_TINT).min(1.0);
+        let [sr, sg, sb] = world.registry().get(CellType::SAND).color;
+        let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * share) as u8;
+        Color32::from_rgb(mix(r, sr), mix(g, sg), mix(b, sb))
+    }
+
+    /// Blue below ambient temperature, black at ambient, then red to yellow.
+    fn heat_color(temperature: f32) -> Color32 {
+        let heat =
+            (temperature - AMBIENT_TEMPERATURE) / (MAX_DRAW_TEMPERATURE - AMBIENT_TEMPERATURE);
+        if heat < 0.0 {
+            let cold = (-heat * 10.0).clamp(0.0, 1.0);
+            Color32::from_rgb(0, 0, (255.0 * cold) as u8)
+        } else {
+            let red = (heat * 2.0).clamp(0.0, 1.0);
+            let green = (heat * 2.0 - 1.0).clamp(0.0, 1.0);
+            Color32::from_rgb((255.0 * red) as u8, (255.0 * green) as u8, 0)
+        }
+    }
+
+    pub fn toggle_heat(&mut self) {
+        self.show_heat = !self.show_heat;
+    }
+
+    pub fn toggle_shift(&mut self) {
+        self.use_shift = !self.use_shift;
+    }
+
+    pub fn update_pos(&mut self) {
+        self.pos_shift.x = -self.block_size * self.shift.x;
+        self.pos_shift.y = -self.block_size * self.shift.y;
+    }
+
+    pub fn gen_shapes(&self, world: &World, shapes: &mut Vec<Shape>, rect: Rect) {
+        let data = &world.data;
+        let registry = world.registry();
+        let is_liquid = |cell: &Cell| registry.get(cell.cell_type).state == State::Liquid;
+
+        for y in 0..data.dim().0 {
+            for x in 0..data.dim().1 {
+                let liquid_above = y > 0 && is_liquid(&data[[y - 1, x]]);
+                let liquid_below = y + 1 < data.dim().0 && is_liquid(&data[[y + 1, x]]);
+
+                // Liquids, drawn partially filled according to their mass
+                if is_liquid(&data[[y, x]])
+                    || (liquid_above && data[[y, x]].cell_type.eq(&CellType::NONE))
+                {
+                    if data[[y, x]].mass > MIN_DRAW {
+                        let liquid = if is_liquid(&data[[y, x]]) {
+                            &data[[y, x]]
+                        } else {
+                            &data[[y - 1, x]]
+                        };
+                        let shift = if !self.use_shift || liquid_above {
+                            0.0
+                        } else {
+                            (1.0 - (data[[y, x]].mass / MAX_MASS)).clamp(0.0, 1.0)
+                        };
+
+                        shapes.push(Shape::rect_filled(
+                            Rect {
+                                min: rect.min
+                                    + vec2(
+                                        self.block_size * x as f32,
+                                        self.block_size * (shift + y as f32),
+                                    )
+                                    + self.pos_shift,
+                                max: rect.min
+                                    + vec2(
+                                        self.block_size * (x + 1) as f32,
+                                        self.block_size * (y + 1) as f32,
+                                    )
+                                    + self.pos_shift,
+                            },
+                            Rounding::none(),
+                            self.color(world, liquid),
+                        ));
+                    }
+                } else if liquid_below
+                    && world.floats_in(data[[y, x]].cell_type, data[[y + 1, x]].cell_type)
+                {
+                    let shift = if self.use_shift {
+                        (1.0 - (data[[y + 1, x]].mass / MAX_MASS)).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+
+                    shapes.push(Shape::rect_filled(
+                        Rect {
+                            min: rect.min
+                                + vec2(
+                                    self.block_size * x as f32,
+                                    self.block_size * (shift + y as f32),
+                                )
+                                + self.pos_shift,
+                            max: rect.min
+                                + vec2(
+                                    self.block_size * (x + 1) as f32,
+                                    self.block_size * (shift + (y + 1) as f32),
+                                )
+                                + self.pos_shift,
+                        },
+                        Rounding::none(),
+                        self.color(world, &data[[y, x]]),
+                    ));
+                } else {
+                    shapes.push(Shape::rect_filled(
+                        Rect {
+                            min: rect.min
+                                + vec2(self.block_size * x as f32, self.block_size * y as f32)
+                                + self.pos_shift,
+                            max: rect.min
+                                + vec2(
+                                    self.block_size * (x + 1) as f32,
+                                    self.block_size * (y + 1) as f32,
+                                )
+                                + self.pos_shift,
+                        },
+                        Rounding::none(),
+                        self.color(world, &data[[y, x]]),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Converts a pointer position into `(y, x)` grid coordinates.
+    pub fn block_pos(&self, pointer_pos: Pos2, clip_rect: Rect) -> (i32, i32) {
+        let block = ((pointer_pos - pos2(clip_rect.left(), clip_rect.top()) - self.pos_shift)
+            / self.block_size)
+            .floor();
+        (block.y as i32, block.x as i32)
+    }
+}
@@ -0,0 +1,63 @@
+/// What happens to material that reaches an edge of the grid.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Boundary {
+    /// The edge is solid; nothing passes it.
+    Wall,
+    /// The edge connects to the opposite one, like a torus.
+    Wrap,
+    /// The edge is open; anything that moves past it is deleted.
+    Open,
+}
+
+impl Boundary {
+    pub const ALL: [Boundary; 3] = [Boundary::Wall, Boundary::Wrap, Boundary::Open];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Boundary::Wall => "Wall",
+            Boundary::Wrap => "Wrap",
+            Boundary::Open => "Open",
+        }
+    }
+}
+
+/// Boundary mode of each edge of the world.
+///
+/// Wrapping is decided by the edge that is crossed: with `top: Wrap` and
+/// `bottom: Wall`, gas rising through the top reappears at the bottom, but
+/// sand cannot fall through the bottom.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Boundaries {
+    pub top: Boundary,
+    pub bottom: Boundary,
+    pub left: Boundary,
+    pub right: Boundary,
+}
+
+impl Boundaries {
+    pub fn all(boundary: Boundary) -> Self {
+        Self {
+            top: boundary,
+            bottom: boundary,
+            left: boundary,
+            right: boundary,
+        }
+    }
+}
+
+impl Default for Boundaries {
+    fn default() -> Self {
+        Boundaries::all(Boundary::Wall)
+    }
+}
+
+/// The result of looking up a neighbouring position with `World::neighbour`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Neighbour {
+    /// A cell inside the grid, as `(y, x)`.
+    Cell(usize, usize),
+    /// Past a `Wall` edge.
+    Wall,
+    /// Past an `Open` edge.
+    Void,
+}
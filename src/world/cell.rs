@@ -0,0 +1,94 @@
+/// Index of a material in the world's `Registry`.
+///
+/// The constants name the built-in materials that the simulation refers to
+/// directly; any other material is looked up through the registry.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct CellType(pub u16);
+
+impl CellType {
+    pub const NONE: CellType = CellType(0);
+    pub const DIRT: CellType = CellType(1);
+    pub const SAND: CellType = CellType(2);
+    pub const WATER: CellType = CellType(3);
+    pub const FIRE_NORMAL: CellType = CellType(4);
+    pub const FIRE_BURN: CellType = CellType(5);
+    pub const SMOKE: CellType = CellType(6);
+    pub const DARK_SMOKE: CellType = CellType(7);
+    pub const WOOD: CellType = CellType(8);
+    pub const STEAM: CellType = CellType(9);
+    pub const LAVA: CellType = CellType(10);
+    pub const STONE: CellType = CellType(11);
+    pub const OIL: CellType = CellType(12);
+    pub const ICE: CellType = CellType(13);
+    pub const ACID: CellType = CellType(14);
+    pub const SEED: CellType = CellType(15);
+    pub const PLANT: CellType = CellType(16);
+    pub const WIRE: CellType = CellType(17);
+    pub const ELECTRON_HEAD: CellType = CellType(18);
+    pub const ELECTRON_TAIL: CellType = CellType(19);
+    pub const POWER: CellType = CellType(20);
+    pub const SWITCH_ON: CellType = CellType(21);
+    pub const SWITCH_OFF: CellType = CellType(22);
+    pub const GUNPOWDER: CellType = CellType(23);
+    pub const EXPLOSION: CellType = CellType(24);
+    pub const EMITTER: CellType = CellType(25);
+    pub const SINK: CellType = CellType(26);
+    pub const WET_SAND: CellType = CellType(27);
+    pub const MUD: CellType = CellType(28);
+    pub const ASH: CellType = CellType(29);
+    pub const CHARCOAL: CellType = CellType(30);
+    pub const EMBERS: CellType = CellType(31);
+}
+
+/// Temperature of a fresh cell, in degrees Celsius.
+pub const AMBIENT_TEMPERATURE: f32 = 20.0;
+
+/// What an `EMITTER` cell spawns, and how fast.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Emission {
+    pub cell_type: CellType,
+    /// For liquids, the share of a full cell added per tick; for anything
+    /// else, the chance per tick of spawning a cell.
+    pub rate: f32,
+}
+
+impl Default for Emission {
+    fn default() -> Self {
+        Self {
+            cell_type: CellType::WATER,
+            rate: 0.5,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Cell {
+    pub x: usize,
+    pub y: usize,
+    pub cell_type: CellType,
+    pub mass: f32,
+    pub temperature: f32,
+    /// Sand carried along by a liquid, in cells' worth.
+    pub sediment: f32,
+    /// Speed as `(dy, dx)` in cells per tick, for materials that build up
+    /// momentum.
+    pub velocity: (f32, f32),
+    /// Only read for `EMITTER` cells. Kept on the cell so that it moves and
+    /// rewinds with it.
+    pub emission: Emission,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            cell_type: CellType::NONE,
+            mass: 0.0,
+            temperature: AMBIENT_TEMPERATURE,
+            sediment: 0.0,
+            velocity: (0.0, 0.0),
+            emission: Emission::default(),
+        }
+    }
+}
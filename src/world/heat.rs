@@ -0,0 +1,79 @@
+use std::mem;
+
+use rand::Rng;
+
+use super::{boundary::Neighbour, World, SMALL_NEIGHBOURHOOD};
+
+/// Upper bound on the share of a temperature difference that flows to one
+/// neighbour per tick. Kept below 1/4 so that diffusion stays stable.
+const DIFFUSION: f32 = 0.2;
+/// Share of the difference to the ambient temperature every cell loses per
+/// tick, so heat does not pile up in closed caves.
+const AMBIENT_LOSS: f32 = 0.005;
+const MIN_TEMPERATURE: f32 = -273.15;
+const MAX_TEMPERATURE: f32 = 3000.0;
+
+impl World {
+    /// Diffuses heat between neighbouring cells, then applies temperature
+    /// driven transitions (ignition, melting, boiling...).
+    ///
+    /// Walls are insulating, open edges exchange heat with ambient air and
+    /// wrapped edges with the opposite side.
+    pub(super) fn update_heat(&mut self) {
+        self.tmp.assign(&self.data);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = self.data[[y, x]];
+                let props = self.registry.get(cell.cell_type);
+                let mut temperature = cell.temperature;
+
+                for step in SMALL_NEIGHBOURHOOD {
+                    let (other, conductivity) = match self.neighbour(y, x, step.0, step.1) {
+                        Neighbour::Cell(ny, nx) => {
+                            let other = &self.data[[ny, nx]];
+                            (
+                                other.temperature,
+                                self.registry.get(other.cell_type).conductivity,
+                            )
+                        }
+                        Neighbour::Void => (self.ambient_temperature, 1.0),
+                        Neighbour::Wall => continue,
+                    };
+
+                    temperature += DIFFUSION
+                        * props.conductivity.min(conductivity)
+                        * (other - cell.temperature);
+                }
+
+                temperature += AMBIENT_LOSS * (self.ambient_temperature - cell.temperature);
+                temperature = temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE);
+                self.tmp[[y, x]].temperature = temperature;
+
+                let mut into = None;
+                if let Some(ignition_point) = props.ignition_point {
+                    if temperature >= ignition_point && self.rng.gen::<f32>() < props.flammability {
+                        into = Some(props.flame);
+                        self.tmp[[y, x]].mass = props.fuel;
+                    }
+                }
+                if let Some(heated) = props.heated {
+                    if temperature >= heated.temperature {
+                        into = Some(heated.into);
+                    }
+                }
+                if let Some(cooled) = props.cooled {
+                    if temperature < cooled.temperature {
+                        into = Some(cooled.into);
+                    }
+                }
+
+                if let Some(into) = into {
+                    self.tmp[[y, x]].cell_type = into;
+                }
+            }
+        }
+
+        mem::swap(&mut self.data, &mut self.tmp);
+    }
+}
@@ -0,0 +1,637 @@
+use std::sync::Arc;
+
+use super::{
+    cell::{CellType, AMBIENT_TEMPERATURE},
+    rules::{
+        Absorbent, Acid, Circuit, Emitter, Explosive, Fire, Floating, Gas, Lava, Liquid, Plant,
+        Powder, Sludge, Steam, Water,
+    },
+    World, MAX_MASS,
+};
+
+/// State of matter, used by renderers and by the passes to decide what a
+/// cell may move into.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum State {
+    Empty,
+    Solid,
+    Powder,
+    Liquid,
+    Gas,
+    Plasma,
+}
+
+/// Update logic for one or more materials.
+///
+/// Each step the world runs one pass per distinct rule, in registration
+/// order: it copies `data` into `tmp`, runs `pass`, which by default calls
+/// `update` for every cell whose material uses the rule, then swaps the two.
+/// Implementations read the previous generation from `world.data` and write
+/// the next into `world.tmp`.
+pub trait Material: Send + Sync {
+    fn update(&self, world: &mut World, y: usize, x: usize);
+
+    /// Runs one pass over the grid. `members` says which `CellType`s use this
+    /// rule. The default calls `update` for every member cell in scan order;
+    /// rules that move whole regions at once override it.
+    fn pass(&self, world: &mut World, members: &[bool]) {
+        for y in 0..world.height() {
+            for x in 0..world.width() {
+                if members[world.data[[y, x]].cell_type.0 as usize] {
+                    self.update(world, y, x);
+                }
+            }
+        }
+    }
+}
+
+/// A rule and a lookup table of which `CellType`s it updates.
+pub type Pass = (Arc<dyn Material>, Vec<bool>);
+
+/// A change of material once a cell crosses a temperature.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Transition {
+    pub temperature: f32,
+    pub into: CellType,
+}
+
+/// Everything the simulation and the front-ends need to know about a material.
+#[derive(Clone)]
+pub struct MaterialProps {
+    pub name: &'static str,
+    /// RGB color used to draw the material.
+    pub color: [u8; 3],
+    /// Heavier materials sink through lighter fluids, lighter ones rise.
+    pub density: f32,
+    pub state: State,
+    /// Chance, per tick, of catching fire once at `ignition_point`.
+    pub flammability: f32,
+    /// Temperature at which a flammable material catches fire.
+    pub ignition_point: Option<f32>,
+    /// Burning material a flammable material turns into once alight.
+    pub flame: CellType,
+    /// Ticks a flammable material keeps burning once alight.
+    pub fuel: f32,
+    /// What a burning material leaves behind once its fuel runs out. `None`
+    /// burns out into smoke.
+    pub residue: Option<CellType>,
+    /// Chance, per tick, that a cell touched by full-strength acid survives.
+    /// `None` is immune to acid.
+    pub acid_resistance: Option<f32>,
+    /// How much a cell weakens a blast passing through it.
+    pub hardness: f32,
+    /// How readily flowing water wears the cell away into sediment, from
+    /// `0.0` (not at all) to `1.0`.
+    pub erodibility: f32,
+    /// Share of a temperature difference exchanged with a neighbour per tick,
+    /// from `0.0` (insulator) to `1.0`.
+    pub conductivity: f32,
+    /// Degrees a burning cell of this material adds to itself and to each of
+    /// its neighbours per tick.
+    pub heat_output: f32,
+    /// Temperature of freshly painted cells.
+    pub initial_temperature: f32,
+    /// Mass of freshly painted cells. Liquids ignore it and fill up as they
+    /// are painted over.
+    pub paint_mass: f32,
+    /// Material this turns into when heated past the transition temperature.
+    pub heated: Option<Transition>,
+    /// Material this turns into when cooled below the transition temperature.
+    pub cooled: Option<Transition>,
+    /// Moves cells of this material each step. `None` never moves.
+    pub rule: Option<Arc<dyn Material>>,
+    /// Whether the material is offered in the palette.
+    pub paintable: bool,
+}
+
+impl Default for MaterialProps {
+    fn default() -> Self {
+        Self {
+            name: "",
+            color: [255, 255, 255],
+            density: 0.0,
+            state: State::Empty,
+            flammability: 0.0,
+            ignition_point: None,
+            flame: CellType::FIRE_BURN,
+            fuel: 0.0,
+            residue: None,
+            acid_resistance: None,
+            hardness: 0.0,
+            erodibility: 0.0,
+            conductivity: 0.5,
+            heat_output: 0.0,
+            initial_temperature: AMBIENT_TEMPERATURE,
+            paint_mass: 0.0,
+            heated: None,
+            cooled: None,
+            rule: None,
+            paintable: false,
+        }
+    }
+}
+
+/// Table of all materials, indexed by `CellType`.
+///
+/// The simulation refers to the built-in materials by their `CellType`
+/// constants, so custom registries start from `Registry::default()` and
+/// `register` their materials after those.
+#[derive(Clone)]
+pub struct Registry {
+    materials: Vec<MaterialProps>,
+}
+
+impl Registry {
+    /// Creates a registry that only knows about empty space.
+    fn empty() -> Self {
+        Self {
+            materials: vec![MaterialProps {
+                name: "Empty",
+                density: 0.02,
+                conductivity: 0.3,
+                paintable: true,
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// Adds a material and returns the id its cells should use.
+    pub fn register(&mut self, props: MaterialProps) -> CellType {
+        self.materials.push(props);
+        CellType((self.materials.len() - 1) as u16)
+    }
+
+    pub fn get(&self, cell_type: CellType) -> &MaterialProps {
+        &self.materials[cell_type.0 as usize]
+    }
+
+    /// For changing a material before the registry is handed to a `World`,
+    /// e.g. to give a built-in one a differently configured rule.
+    pub fn get_mut(&mut self, cell_type: CellType) -> &mut MaterialProps {
+        &mut self.materials[cell_type.0 as usize]
+    }
+
+    pub fn find(&self, name: &str) -> Option<CellType> {
+        self.materials
+            .iter()
+            .position(|props| props.name == name)
+            .map(|index| CellType(index as u16))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (CellType, &MaterialProps)> {
+        self.materials
+            .iter()
+            .enumerate()
+            .map(|(index, props)| (CellType(index as u16), props))
+    }
+
+    /// The distinct rules in registration order, each with a lookup table of
+    /// which `CellType`s it updates.
+    pub fn passes(&self) -> Vec<Pass> {
+        let mut passes: Vec<Pass> = Vec::new();
+
+        for (index, props) in self.materials.iter().enumerate() {
+            if let Some(rule) = &props.rule {
+                match passes.iter_mut().find(|(r, _)| Arc::ptr_eq(r, rule)) {
+                    Some((_, members)) => members[index] = true,
+                    None => {
+                        let mut members = vec![false; self.materials.len()];
+                        members[index] = true;
+                        passes.push((rule.clone(), members));
+                    }
+                }
+            }
+        }
+
+        passes
+    }
+
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+}
+
+impl Default for Registry {
+    /// The built-in materials. Registration order must match the ids in
+    /// `CellType`.
+    fn default() -> Self {
+        let mut registry = Registry::empty();
+        let fire: Arc<dyn Material> = Arc::new(Fire);
+        let gas: Arc<dyn Material> = Arc::new(Gas);
+
+        registry.register(MaterialProps {
+            name: "Dirt",
+            color: [0, 0, 0],
+            density: 3.0,
+            state: State::Solid,
+            acid_resistance: Some(0.9),
+            hardness: 3.0,
+            erodibility: 0.1,
+            conductivity: 0.2,
+            rule: Some(Arc::new(Absorbent {
+                dry: CellType::DIRT,
+                wet: CellType::MUD,
+                capacity: 4.0,
+                rate: 0.05,
+                motion: None,
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Sand",
+            color: [255, 215, 0],
+            density: 2.0,
+            state: State::Powder,
+            acid_resistance: Some(0.8),
+            hardness: 0.5,
+            erodibility: 1.0,
+            conductivity: 0.2,
+            rule: Some(Arc::new(Absorbent {
+                dry: CellType::SAND,
+                wet: CellType::WET_SAND,
+                capacity: 3.0,
+                rate: 0.2,
+                motion: Some(Arc::new(Powder::default())),
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Water",
+            color: [0, 0, 255],
+            density: 1.0,
+            state: State::Liquid,
+            conductivity: 0.6,
+            heated: Some(Transition {
+                temperature: 100.0,
+                into: CellType::STEAM,
+            }),
+            cooled: Some(Transition {
+                temperature: 0.0,
+                into: CellType::ICE,
+            }),
+            rule: Some(Arc::new(Water {
+                liquid: Liquid::default(),
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Fire",
+            color: [255, 128, 128],
+            density: 0.1,
+            state: State::Plasma,
+            conductivity: 1.0,
+            heat_output: 300.0,
+            initial_temperature: 1000.0,
+            rule: Some(fire.clone()),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Burning wood",
+            color: [139, 0, 0],
+            density: 0.6,
+            state: State::Plasma,
+            conductivity: 1.0,
+            heat_output: 60.0,
+            residue: Some(CellType::CHARCOAL),
+            rule: Some(fire.clone()),
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Smoke",
+            color: [220, 220, 220],
+            density: 0.01,
+            state: State::Gas,
+            conductivity: 0.8,
+            rule: Some(gas.clone()),
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Dark smoke",
+            color: [96, 96, 96],
+            density: 0.01,
+            state: State::Gas,
+            conductivity: 0.8,
+            rule: Some(gas),
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Wood",
+            color: [165, 42, 42],
+            density: 0.6,
+            state: State::Solid,
+            flammability: 0.2,
+            ignition_point: Some(250.0),
+            fuel: 60.0,
+            acid_resistance: Some(0.7),
+            hardness: 1.0,
+            conductivity: 0.8,
+            rule: Some(Arc::new(Floating)),
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Steam",
+            color: [200, 225, 255],
+            density: 0.005,
+            state: State::Gas,
+            conductivity: 0.05,
+            initial_temperature: 120.0,
+            paint_mass: 1.0,
+            cooled: Some(Transition {
+                temperature: 80.0,
+                into: CellType::WATER,
+            }),
+            rule: Some(Arc::new(Steam)),
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Lava",
+            color: [255, 69, 0],
+            density: 3.0,
+            state: State::Liquid,
+            conductivity: 0.05,
+            initial_temperature: 1200.0,
+            cooled: Some(Transition {
+                temperature: 400.0,
+                into: CellType::STONE,
+            }),
+            rule: Some(Arc::new(Lava {
+                liquid: Liquid {
+                    viscosity: 0.8,
+                    ..Default::default()
+                },
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Stone",
+            color: [112, 112, 112],
+            density: 3.0,
+            state: State::Solid,
+            hardness: 6.0,
+            conductivity: 0.3,
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Oil",
+            color: [70, 45, 20],
+            density: 0.8,
+            state: State::Liquid,
+            flammability: 0.9,
+            ignition_point: Some(200.0),
+            flame: CellType::FIRE_NORMAL,
+            fuel: 10.0,
+            conductivity: 0.4,
+            rule: Some(Arc::new(Liquid {
+                viscosity: 0.3,
+                min_flow: 0.2,
+                max_flow: 2.0,
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Ice",
+            color: [175, 225, 255],
+            density: 0.9,
+            state: State::Solid,
+            hardness: 1.0,
+            conductivity: 0.7,
+            initial_temperature: -20.0,
+            paint_mass: MAX_MASS,
+            heated: Some(Transition {
+                temperature: 0.0,
+                into: CellType::WATER,
+            }),
+            rule: Some(Arc::new(Floating)),
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Acid",
+            color: [120, 255, 0],
+            density: 1.1,
+            state: State::Liquid,
+            conductivity: 0.6,
+            rule: Some(Arc::new(Acid {
+                liquid: Liquid::default(),
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+
+        let plant: Arc<dyn Material> = Arc::new(Plant::default());
+        registry.register(MaterialProps {
+            name: "Seed",
+            color: [150, 110, 60],
+            density: 1.5,
+            state: State::Powder,
+            flammability: 0.3,
+            ignition_point: Some(200.0),
+            flame: CellType::EMBERS,
+            fuel: 10.0,
+            acid_resistance: Some(0.5),
+            conductivity: 0.5,
+            rule: Some(plant.clone()),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Plant",
+            color: [34, 139, 34],
+            density: 0.7,
+            state: State::Solid,
+            flammability: 0.3,
+            ignition_point: Some(200.0),
+            flame: CellType::EMBERS,
+            fuel: 20.0,
+            acid_resistance: Some(0.5),
+            hardness: 0.5,
+            conductivity: 0.5,
+            rule: Some(plant),
+            ..Default::default()
+        });
+
+        let circuit: Arc<dyn Material> = Arc::new(Circuit::default());
+        let conductor = |name, color, paintable| MaterialProps {
+            name,
+            color,
+            density: 5.0,
+            state: State::Solid,
+            hardness: 2.0,
+            conductivity: 1.0,
+            rule: Some(circuit.clone()),
+            paintable,
+            ..Default::default()
+        };
+        registry.register(conductor("Wire", [184, 115, 51], true));
+        registry.register(conductor("Electron head", [0, 128, 255], true));
+        registry.register(conductor("Electron tail", [255, 64, 0], false));
+        registry.register(conductor("Power", [255, 0, 255], true));
+        registry.register(MaterialProps {
+            name: "Switch (on)",
+            color: [0, 200, 0],
+            density: 5.0,
+            state: State::Solid,
+            hardness: 2.0,
+            conductivity: 1.0,
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Switch (off)",
+            color: [120, 0, 0],
+            ..registry.get(CellType::SWITCH_ON).clone()
+        });
+
+        registry.register(MaterialProps {
+            name: "Gunpowder",
+            color: [64, 64, 64],
+            density: 1.8,
+            state: State::Powder,
+            ignition_point: Some(150.0),
+            acid_resistance: Some(0.5),
+            hardness: 0.5,
+            conductivity: 0.3,
+            heated: Some(Transition {
+                temperature: 150.0,
+                into: CellType::EXPLOSION,
+            }),
+            rule: Some(Arc::new(Powder::default())),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Explosion",
+            color: [255, 255, 160],
+            density: 0.1,
+            state: State::Plasma,
+            conductivity: 1.0,
+            rule: Some(Arc::new(Explosive { radius: 8.0 })),
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Emitter",
+            color: [0, 160, 160],
+            density: 5.0,
+            state: State::Solid,
+            hardness: 10.0,
+            conductivity: 0.0,
+            rule: Some(Arc::new(Emitter)),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Sink",
+            color: [40, 0, 60],
+            density: 5.0,
+            state: State::Solid,
+            hardness: 10.0,
+            conductivity: 0.0,
+            paintable: true,
+            ..Default::default()
+        });
+
+        registry.register(MaterialProps {
+            name: "Wet sand",
+            color: [190, 150, 20],
+            density: 2.2,
+            state: State::Powder,
+            acid_resistance: Some(0.8),
+            hardness: 1.0,
+            erodibility: 0.7,
+            conductivity: 0.5,
+            paint_mass: 3.0,
+            rule: Some(Arc::new(Absorbent {
+                dry: CellType::SAND,
+                wet: CellType::WET_SAND,
+                capacity: 3.0,
+                rate: 0.2,
+                motion: Some(Arc::new(Powder { steepness: 2 })),
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Mud",
+            color: [100, 65, 35],
+            density: 2.5,
+            state: State::Powder,
+            acid_resistance: Some(0.9),
+            hardness: 1.0,
+            erodibility: 0.8,
+            conductivity: 0.5,
+            paint_mass: 4.0,
+            rule: Some(Arc::new(Absorbent {
+                dry: CellType::DIRT,
+                wet: CellType::MUD,
+                capacity: 4.0,
+                rate: 0.05,
+                motion: Some(Arc::new(Sludge { fluidity: 0.05 })),
+            })),
+            paintable: true,
+            ..Default::default()
+        });
+
+        let light_powder: Arc<dyn Material> = Arc::new(Powder::default());
+        registry.register(MaterialProps {
+            name: "Ash",
+            color: [180, 180, 175],
+            density: 0.4,
+            state: State::Powder,
+            acid_resistance: Some(0.3),
+            erodibility: 1.0,
+            conductivity: 0.1,
+            rule: Some(light_powder.clone()),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Charcoal",
+            color: [45, 40, 40],
+            density: 0.5,
+            state: State::Powder,
+            flammability: 0.05,
+            ignition_point: Some(350.0),
+            flame: CellType::EMBERS,
+            fuel: 100.0,
+            acid_resistance: Some(0.6),
+            hardness: 0.5,
+            conductivity: 0.2,
+            rule: Some(light_powder),
+            paintable: true,
+            ..Default::default()
+        });
+        registry.register(MaterialProps {
+            name: "Embers",
+            color: [255, 90, 20],
+            density: 0.5,
+            state: State::Plasma,
+            conductivity: 1.0,
+            heat_output: 30.0,
+            residue: Some(CellType::ASH),
+            rule: Some(fire),
+            ..Default::default()
+        });
+
+        debug_assert_eq!(registry.find("Embers"), Some(CellType::EMBERS));
+        registry
+    }
+}
@@ -0,0 +1,454 @@
+pub mod boundary;
+pub mod cell;
+mod heat;
+pub mod material;
+pub mod rules;
+pub mod timeline;
+
+use std::{mem, sync::Arc};
+
+use ndarray::Array2;
+
+use boundary::{Boundaries, Boundary, Neighbour};
+use cell::*;
+use material::{Pass, Registry, State};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use timeline::Snapshot;
+
+/// Mass of a full liquid cell.
+pub const MAX_MASS: f32 = 10.0;
+/// Speed, in cells per tick, a falling cell gains every tick.
+pub const GRAVITY: f32 = 0.2;
+/// Fastest anything moves, in cells per tick.
+pub const MAX_SPEED: f32 = 8.0;
+
+const NEIGHBOURHOOD: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, -1),
+    (0, 1),
+];
+
+pub(crate) const SMALL_NEIGHBOURHOOD: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Rendering-free simulation state: the cell grid, the update passes and
+/// the cave generator. Front-ends (such as the egui `App`) read `data` to draw
+/// it and call `step`/`transform_cell` to drive it.
+pub struct World {
+    pub data: Array2<Cell>,
+    pub tmp: Array2<Cell>,
+    pub threshold: f32,
+    pub boundaries: Boundaries,
+    /// Temperature of the surrounding air, which every cell slowly tends to.
+    pub ambient_temperature: f32,
+    registry: Registry,
+    /// `registry.passes()`, worked out once as the registry cannot change.
+    passes: Arc<Vec<Pass>>,
+    width: usize,
+    height: usize,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl World {
+    /// Creates an empty world `width` cells wide and `height` cells tall,
+    /// seeded from entropy. The grid is indexed as `[[y, x]]`.
+    pub fn new(width: usize, height: usize) -> Self {
+        // Kept to 32 bits so the seed round-trips through UI number fields.
+        World::with_seed(width, height, rand::random::<u32>() as u64)
+    }
+
+    /// Like `new`, but with a fixed seed so that cave generation and the
+    /// simulation history are reproducible.
+    pub fn with_seed(width: usize, height: usize, seed: u64) -> Self {
+        World::with_registry(width, height, seed, Registry::default())
+    }
+
+    /// Creates a world that simulates the materials in `registry`, which may
+    /// include materials (and `Material` rules) defined outside this crate.
+    /// It has to start from `Registry::default()`, since the built-in rules
+    /// use the `CellType` constants.
+    pub fn with_registry(width: usize, height: usize, seed: u64, registry: Registry) -> Self {
+        let mut _data = Array2::<Cell>::default((height, width));
+        let mut _tmp = Array2::<Cell>::default((height, width));
+
+        /*
+            Fix cell position
+        */
+        for y in 0.._data.dim().0 {
+            for x in 0.._data.dim().1 {
+                _data[[y, x]].x = x;
+                _data[[y, x]].y = y;
+                _tmp[[y, x]].x = x;
+                _tmp[[y, x]].y = y;
+            }
+        }
+
+        Self {
+            data: _data,
+            tmp: _tmp,
+            threshold: 0.5,
+            boundaries: Boundaries::default(),
+            ambient_temperature: AMBIENT_TEMPERATURE,
+            passes: Arc::new(registry.passes()),
+            registry,
+            width,
+            height,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn bounds_valid(&self, y: i32, x: i32) -> bool {
+        y >= 0 && x >= 0 && (y as usize) < self.height && (x as usize) < self.width
+    }
+
+    /// Looks up the position `(dy, dx)` away from `(y, x)`, resolving
+    /// positions outside the grid according to `boundaries`. Sink cells
+    /// behave like an open edge, so whatever moves into them is lost.
+    pub fn neighbour(&self, y: usize, x: usize, dy: i32, dx: i32) -> Neighbour {
+        let height = self.height as i32;
+        let width = self.width as i32;
+        let mut ny = y as i32 + dy;
+        let mut nx = x as i32 + dx;
+
+        let crossed = if ny < 0 {
+            Some(self.boundaries.top)
+        } else if ny >= height {
+            Some(self.boundaries.bottom)
+        } else {
+            None
+        };
+        match crossed {
+            Some(Boundary::Wall) => return Neighbour::Wall,
+            Some(Boundary::Open) => return Neighbour::Void,
+            Some(Boundary::Wrap) => ny = ny.rem_euclid(height),
+            None => {}
+        }
+
+        let crossed = if nx < 0 {
+            Some(self.boundaries.left)
+        } else if nx >= width {
+            Some(self.boundaries.right)
+        } else {
+            None
+        };
+        match crossed {
+            Some(Boundary::Wall) => return Neighbour::Wall,
+            Some(Boundary::Open) => return Neighbour::Void,
+            Some(Boundary::Wrap) => nx = nx.rem_euclid(width),
+            None => {}
+        }
+
+        if self.data[[ny as usize, nx as usize]]
+            .cell_type
+            .eq(&CellType::SINK)
+        {
+            return Neighbour::Void;
+        }
+
+        Neighbour::Cell(ny as usize, nx as usize)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Stores `seed` and restarts the random number generator from it.
+    /// `StdRng` is platform independent, so native and wasm builds agree.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            data: self.data.clone(),
+            rng: self.rng.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.data = snapshot.data.clone();
+        self.tmp = snapshot.data.clone();
+        self.rng = snapshot.rng.clone();
+        self.height = self.data.dim().0;
+        self.width = self.data.dim().1;
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(y, x)` lies on an edge with a `Wall` boundary, where
+    /// `add_border` puts its Dirt.
+    pub fn is_border(&self, y: usize, x: usize) -> bool {
+        (y == 0 && self.boundaries.top.eq(&Boundary::Wall))
+            || (y == self.height - 1 && self.boundaries.bottom.eq(&Boundary::Wall))
+            || (x == 0 && self.boundaries.left.eq(&Boundary::Wall))
+            || (x == self.width - 1 && self.boundaries.right.eq(&Boundary::Wall))
+    }
+
+    /// Paints a line of Dirt along every `Wall` edge, so generated caves look
+    /// closed. The simulation itself does not need it.
+    pub fn add_border(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.is_border(y, x) {
+                    self.data[[y, x]].cell_type = CellType::DIRT;
+                }
+            }
+        }
+    }
+
+    pub fn rand_generate(&mut self) {
+        for cell in &mut self.data {
+            cell.cell_type = if self.rng.gen::<f32>() < self.threshold {
+                CellType::DIRT
+            } else {
+                CellType::NONE
+            };
+            cell.mass = 0.0;
+            cell.temperature = self.ambient_temperature;
+            cell.sediment = 0.0;
+            cell.velocity = (0.0, 0.0);
+        }
+        self.add_border();
+    }
+
+    /// One round of cave smoothing: a cell becomes Dirt with more than four
+    /// Dirt neighbours and empty with fewer. Walls count as Dirt, open edges
+    /// as empty and wrapped edges sample the opposite side.
+    pub fn smooth(&mut self) {
+        let mut sum;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.data[[y, x]].cell_type.ne(&CellType::DIRT)
+                    && self.data[[y, x]].cell_type.ne(&CellType::NONE)
+                {
+                    self.tmp[[y, x]].cell_type = self.data[[y, x]].cell_type;
+                    continue;
+                }
+
+                sum = 0;
+                for step in NEIGHBOURHOOD {
+                    let is_dirt = match self.neighbour(y, x, step.0, step.1) {
+                        Neighbour::Cell(ny, nx) => self.data[[ny, nx]].cell_type == CellType::DIRT,
+                        Neighbour::Wall => true,
+                        Neighbour::Void => false,
+                    };
+                    if is_dirt {
+                        sum += 1;
+                    }
+                }
+
+                self.tmp[[y, x]].cell_type = if sum > 4 {
+                    CellType::DIRT
+                } else if sum < 4 {
+                    CellType::NONE
+                } else {
+                    self.data[[y, x]].cell_type
+                };
+            }
+        }
+        mem::swap(&mut self.data, &mut self.tmp);
+    }
+
+    pub fn state(&self, cell_type: CellType) -> State {
+        self.registry.get(cell_type).state
+    }
+
+    /// Whether `cell_type` gives way to heavier material sinking into it and
+    /// lighter material rising through it. Solids and powders hold their place.
+    pub fn is_fluid(&self, cell_type: CellType) -> bool {
+        matches!(
+            self.state(cell_type),
+            State::Empty | State::Liquid | State::Gas | State::Plasma
+        )
+    }
+
+    /// Swaps the contents (everything but the position) of two cells in `tmp`.
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let first = self.tmp[[a.0, a.1]];
+        let second = self.tmp[[b.0, b.1]];
+
+        self.tmp[[a.0, a.1]] = Cell {
+            x: first.x,
+            y: first.y,
+            ..second
+        };
+        self.tmp[[b.0, b.1]] = Cell {
+            x: second.x,
+            y: second.y,
+            ..first
+        };
+    }
+
+    /// Empties `(y, x)` in `tmp`, e.g. after its content left the grid.
+    pub fn vacate(&mut self, y: usize, x: usize) {
+        self.tmp[[y, x]].cell_type = CellType::NONE;
+        self.tmp[[y, x]].mass = 0.0;
+        self.tmp[[y, x]].sediment = 0.0;
+        self.tmp[[y, x]].velocity = (0.0, 0.0);
+    }
+
+    /// Whether `cell_type` may trade places with what is at `neighbour` in
+    /// `tmp` by sinking into it: a fluid lighter than itself. Open edges always
+    /// give way, walls never do.
+    pub fn sinks_into(&self, cell_type: CellType, neighbour: Neighbour) -> bool {
+        self.displaces(cell_type, neighbour, |density, other| density > other)
+    }
+
+    /// Whether `cell_type` may trade places with what is at `neighbour` in
+    /// `tmp` by rising through it: a fluid heavier than itself.
+    pub fn rises_into(&self, cell_type: CellType, neighbour: Neighbour) -> bool {
+        self.displaces(cell_type, neighbour, |density, other| density < other)
+    }
+
+    fn displaces(
+        &self,
+        cell_type: CellType,
+        neighbour: Neighbour,
+        order: impl Fn(f32, f32) -> bool,
+    ) -> bool {
+        match neighbour {
+            Neighbour::Cell(y, x) => {
+                let other = self.tmp[[y, x]].cell_type;
+                self.is_fluid(other)
+                    && order(
+                        self.registry.get(cell_type).density,
+                        self.registry.get(other).density,
+                    )
+            }
+            Neighbour::Wall => false,
+            Neighbour::Void => true,
+        }
+    }
+
+    /// Follows `velocity` from `(y, x)` for as many cells as its speed, at
+    /// least one, and returns the furthest cell `cell_type` can sink into
+    /// without passing anything it cannot. `None` if the first one is
+    /// already blocked.
+    pub fn trace(
+        &self,
+        y: usize,
+        x: usize,
+        cell_type: CellType,
+        velocity: (f32, f32),
+    ) -> Option<Neighbour> {
+        let speed = velocity.0.abs().max(velocity.1.abs());
+        if speed <= 0.0 {
+            return None;
+        }
+
+        let (dy, dx) = (velocity.0 / speed, velocity.1 / speed);
+        let mut reached = None;
+        for step in 1..=(speed.round() as i32).max(1) {
+            let next = self.neighbour(
+                y,
+                x,
+                (dy * step as f32).round() as i32,
+                (dx * step as f32).round() as i32,
+            );
+            if !self.sinks_into(cell_type, next) {
+                break;
+            }
+
+            reached = Some(next);
+            if next.eq(&Neighbour::Void) {
+                break;
+            }
+        }
+
+        reached
+    }
+
+    /// Whether `cell_type` is a solid light enough to float on `liquid`.
+    pub fn floats_in(&self, cell_type: CellType, liquid: CellType) -> bool {
+        let props = self.registry.get(cell_type);
+        props.state.eq(&State::Solid) && props.density < self.registry.get(liquid).density
+    }
+
+    /// The world's random number generator. Rules must draw from it, not from
+    /// `thread_rng`, to keep runs reproducible.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Advances the simulation by exactly one generation.
+    pub fn step(&mut self) {
+        self.update_heat();
+
+        let passes = Arc::clone(&self.passes);
+        for (rule, members) in passes.iter() {
+            self.tmp.assign(&self.data);
+            rule.pass(self, members);
+            mem::swap(&mut self.data, &mut self.tmp);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for cell in &mut self.data {
+            cell.cell_type = CellType::NONE;
+            cell.mass = 0.0;
+            cell.temperature = self.ambient_temperature;
+            cell.sediment = 0.0;
+            cell.velocity = (0.0, 0.0);
+        }
+
+        self.add_border();
+    }
+
+    /// The settings of the emitter at `(y, x)`, if there is one.
+    pub fn emission_mut(&mut self, y: i32, x: i32) -> Option<&mut Emission> {
+        if !self.bounds_valid(y, x) {
+            return None;
+        }
+
+        let cell = &mut self.data[[y as usize, x as usize]];
+        cell.cell_type
+            .eq(&CellType::EMITTER)
+            .then_some(&mut cell.emission)
+    }
+
+    /// Paints `cell_type` at `(y, x)`. Painting a liquid adds mass instead of
+    /// overwriting, so holding the pointer down fills the cell up.
+    pub fn transform_cell(&mut self, y: i32, x: i32, cell_type: CellType) {
+        if !self.bounds_valid(y, x) {
+            return;
+        }
+
+        let props = self.registry.get(cell_type);
+        let is_liquid = props.state.eq(&State::Liquid);
+        let mass = props.paint_mass;
+        let temperature = props.initial_temperature;
+        let cell = &mut self.data[[y as usize, x as usize]];
+        cell.velocity = (0.0, 0.0);
+        if !is_liquid {
+            cell.cell_type = cell_type;
+            cell.mass = mass;
+            cell.temperature = temperature;
+            cell.sediment = 0.0;
+        } else if cell.cell_type.eq(&CellType::NONE) {
+            cell.cell_type = cell_type;
+            cell.mass = 1.0;
+            cell.temperature = temperature;
+            cell.sediment = 0.0;
+        } else if cell.cell_type.eq(&cell_type) {
+            cell.mass += 2.0;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour, cell::CellType, material::Material, World, SMALL_NEIGHBOURHOOD,
+};
+
+/// Chance per tick that a wet cell at ambient temperature gives off water.
+const DRYING: f32 = 0.005;
+/// Degrees above ambient temperature that double the chance of drying.
+const DRYING_HEAT: f32 = 10.0;
+/// Most water given off as steam at once.
+const VAPOUR: f32 = 1.0;
+/// Liquid left behind below this is soaked up as well, so that it does not
+/// evaporate in `Liquid`'s cleanup.
+const MIN_MASS: f32 = 0.01;
+
+/// Soaks up water from neighbouring Water cells into its mass, turning into
+/// `wet`, and gives it back as steam over time, or faster when heated, until
+/// it turns back into `dry`. The water stays accounted for in both
+/// directions. Moves according to `motion`, if any.
+pub struct Absorbent {
+    pub dry: CellType,
+    pub wet: CellType,
+    /// Most water mass one cell holds.
+    pub capacity: f32,
+    /// Water mass soaked up per tick.
+    pub rate: f32,
+    pub motion: Option<Arc<dyn Material>>,
+}
+
+impl Absorbent {
+    fn soak(&self, world: &mut World, y: usize, x: usize) {
+        for (dy, dx) in SMALL_NEIGHBOURHOOD {
+            let room = self.capacity - world.tmp[[y, x]].mass;
+            if room <= 0.0 {
+                return;
+            }
+
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, dy, dx) {
+                let water = world.tmp[[ny, nx]];
+                if water.cell_type.ne(&CellType::WATER)
+                    || world.data[[ny, nx]].cell_type.ne(&CellType::WATER)
+                {
+                    continue;
+                }
+
+                let mut taken = self.rate.min(room).min(water.mass);
+                if water.mass - taken < MIN_MASS {
+                    taken = water.mass;
+                    world.vacate(ny, nx);
+                } else {
+                    world.tmp[[ny, nx]].mass -= taken;
+                }
+
+                world.tmp[[y, x]].mass += taken;
+                world.tmp[[y, x]].cell_type = self.wet;
+            }
+        }
+    }
+
+    fn dry_out(&self, world: &mut World, y: usize, x: usize) {
+        let cell = world.tmp[[y, x]];
+        let heat = (cell.temperature - world.ambient_temperature).max(0.0) / DRYING_HEAT;
+        if world.rng().gen::<f32>() >= DRYING * (1.0 + heat) {
+            return;
+        }
+
+        for (dy, dx) in [(-1, 0), (0, -1), (0, 1)] {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, dy, dx) {
+                if world.tmp[[ny, nx]].cell_type.ne(&CellType::NONE) {
+                    continue;
+                }
+
+                let vapour = cell.mass.min(VAPOUR);
+                let temperature = world.registry().get(CellType::STEAM).initial_temperature;
+                let steam = &mut world.tmp[[ny, nx]];
+                steam.cell_type = CellType::STEAM;
+                steam.mass = vapour;
+                steam.sediment = 0.0;
+                steam.temperature = temperature.max(cell.temperature);
+
+                let rest = &mut world.tmp[[y, x]];
+                rest.mass -= vapour;
+                if rest.mass <= 0.0 {
+                    rest.mass = 0.0;
+                    rest.cell_type = self.dry;
+                }
+                return;
+            }
+        }
+    }
+}
+
+impl Material for Absorbent {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        // Keep the edges of walled caves in place
+        if !world.is_border(y, x) {
+            self.soak(world, y, x);
+            if world.tmp[[y, x]].mass > 0.0 {
+                self.dry_out(world, y, x);
+            }
+        }
+
+        if let Some(motion) = &self.motion {
+            motion.update(world, y, x);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour, cell::CellType, material::Material, World, MAX_MASS, SMALL_NEIGHBOURHOOD,
+};
+
+use super::Liquid;
+
+/// Acid mass used up by every cell it dissolves.
+const DISSOLVE_COST: f32 = 1.0;
+/// Mass of the smoke a dissolved cell turns into.
+const FUMES: f32 = 1.0;
+
+/// Flows like `liquid` and eats through neighbours that have an
+/// `acid_resistance`, apart from the Dirt border of walled edges, turning
+/// them into smoke, or Wet sand and Mud into the water they held. Every
+/// dissolved cell costs it mass, and the less mass a cell holds the weaker it
+/// bites.
+pub struct Acid {
+    pub liquid: Liquid,
+}
+
+impl Material for Acid {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let acid = world.data[[y, x]].cell_type;
+        self.liquid.update(world, y, x);
+
+        for step in SMALL_NEIGHBOURHOOD {
+            // Moved, used up or flowed away
+            if world.tmp[[y, x]].cell_type.ne(&acid) || world.tmp[[y, x]].mass < DISSOLVE_COST {
+                return;
+            }
+
+            let (ny, nx) = match world.neighbour(y, x, step.0, step.1) {
+                Neighbour::Cell(ny, nx) if !world.is_border(ny, nx) => (ny, nx),
+                _ => continue,
+            };
+
+            let resistance = match world
+                .registry()
+                .get(world.tmp[[ny, nx]].cell_type)
+                .acid_resistance
+            {
+                Some(resistance) => resistance,
+                None => continue,
+            };
+
+            let strength = (world.tmp[[y, x]].mass / MAX_MASS).min(1.0);
+            if world.rng().gen::<f32>() < (1.0 - resistance) * strength {
+                let cell = &mut world.tmp[[ny, nx]];
+                if matches!(cell.cell_type, CellType::WET_SAND | CellType::MUD) {
+                    // The water it soaked up is set free
+                    cell.cell_type = CellType::WATER;
+                    cell.velocity = (0.0, 0.0);
+                } else {
+                    cell.cell_type = CellType::SMOKE;
+                    cell.mass = FUMES;
+                }
+                world.tmp[[y, x]].mass -= DISSOLVE_COST;
+            }
+        }
+    }
+}
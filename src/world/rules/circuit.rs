@@ -0,0 +1,94 @@
+use crate::world::{
+    boundary::Neighbour, cell::CellType, material::Material, World, NEIGHBOURHOOD,
+    SMALL_NEIGHBOURHOOD,
+};
+
+/// Degrees past its ignition point a spark heats a flammable neighbour, so it
+/// is still hot enough to catch once heat has spread.
+const SPARK: f32 = 200.0;
+
+/// WireWorld: an electron head turns into a tail, a tail back into wire, and
+/// wire becomes a head when one or two of its eight neighbours are heads.
+///
+/// A closed switch bridges the wires on both sides, counting as a head while
+/// it touches one; an open switch blocks. Power sources send a head into the
+/// wire next to them every `period` ticks, counting in their mass. Heads heat
+/// flammable neighbours past their ignition point, see `World::update_heat`.
+pub struct Circuit {
+    pub period: u32,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self { period: 8 }
+    }
+}
+
+/// The eight neighbours of `(y, x)` inside the grid.
+fn neighbours(world: &World, y: usize, x: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    NEIGHBOURHOOD
+        .iter()
+        .filter_map(move |&(dy, dx)| match world.neighbour(y, x, dy, dx) {
+            Neighbour::Cell(ny, nx) => Some((ny, nx)),
+            _ => None,
+        })
+}
+
+fn is_head(world: &World, y: usize, x: usize) -> bool {
+    world.data[[y, x]].cell_type.eq(&CellType::ELECTRON_HEAD)
+}
+
+fn heads(world: &World, y: usize, x: usize) -> usize {
+    neighbours(world, y, x)
+        .filter(|&(ny, nx)| {
+            is_head(world, ny, nx)
+                || (world.data[[ny, nx]].cell_type.eq(&CellType::SWITCH_ON)
+                    && neighbours(world, ny, nx).any(|(sy, sx)| is_head(world, sy, sx)))
+        })
+        .count()
+}
+
+fn spark(world: &mut World, y: usize, x: usize) {
+    for (ny, nx) in neighbours(world, y, x).collect::<Vec<_>>() {
+        if let Some(ignition_point) = world
+            .registry()
+            .get(world.tmp[[ny, nx]].cell_type)
+            .ignition_point
+        {
+            let cell = &mut world.tmp[[ny, nx]];
+            cell.temperature = cell.temperature.max(ignition_point + SPARK);
+        }
+    }
+}
+
+impl Material for Circuit {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        match world.data[[y, x]].cell_type {
+            CellType::WIRE if (1..=2).contains(&heads(world, y, x)) => {
+                world.tmp[[y, x]].cell_type = CellType::ELECTRON_HEAD;
+            }
+            CellType::ELECTRON_HEAD => {
+                world.tmp[[y, x]].cell_type = CellType::ELECTRON_TAIL;
+                spark(world, y, x);
+            }
+            CellType::ELECTRON_TAIL => world.tmp[[y, x]].cell_type = CellType::WIRE,
+            CellType::POWER => {
+                let ticks = world.data[[y, x]].mass + 1.0;
+                if ticks < self.period as f32 {
+                    world.tmp[[y, x]].mass = ticks;
+                    return;
+                }
+
+                world.tmp[[y, x]].mass = 0.0;
+                for step in SMALL_NEIGHBOURHOOD {
+                    if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, step.0, step.1) {
+                        if world.data[[ny, nx]].cell_type.eq(&CellType::WIRE) {
+                            world.tmp[[ny, nx]].cell_type = CellType::ELECTRON_HEAD;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
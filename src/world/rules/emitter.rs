@@ -0,0 +1,52 @@
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour,
+    cell::CellType,
+    material::{Material, State},
+    World, MAX_MASS,
+};
+
+/// Spawns the material in the cell's `Emission` next to it: below for
+/// anything heavier than air, above otherwise. Liquids top up the cell there
+/// to at most a full one; other materials only fill it when it is empty.
+pub struct Emitter;
+
+impl Material for Emitter {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let emission = world.data[[y, x]].emission;
+        let props = world.registry().get(emission.cell_type);
+        let (is_liquid, paint_mass, temperature) = (
+            props.state.eq(&State::Liquid),
+            props.paint_mass,
+            props.initial_temperature,
+        );
+        let dy = if props.density > world.registry().get(CellType::NONE).density {
+            1
+        } else {
+            -1
+        };
+
+        let (ty, tx) = match world.neighbour(y, x, dy, 0) {
+            Neighbour::Cell(ty, tx) => (ty, tx),
+            _ => return,
+        };
+        let target = &mut world.tmp[[ty, tx]];
+
+        if is_liquid {
+            if target.cell_type.eq(&CellType::NONE) {
+                target.cell_type = emission.cell_type;
+                target.mass = 0.0;
+                target.temperature = temperature;
+            }
+            if target.cell_type.eq(&emission.cell_type) && target.mass < MAX_MASS {
+                target.mass = (target.mass + emission.rate * MAX_MASS).min(MAX_MASS);
+            }
+        } else if target.cell_type.eq(&CellType::NONE) && world.rng().gen::<f32>() < emission.rate {
+            let target = &mut world.tmp[[ty, tx]];
+            target.cell_type = emission.cell_type;
+            target.mass = paint_mass;
+            target.temperature = temperature;
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::{collections::HashSet, f32::consts::TAU};
+
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour,
+    cell::{Cell, CellType},
+    material::{Material, State},
+    World,
+};
+
+/// Temperature everything the blast reaches is heated to.
+const BLAST_HEAT: f32 = 600.0;
+/// Chance that a destroyed cell is thrown to the edge of the blast.
+const DEBRIS: f32 = 0.3;
+/// Chance that a destroyed cell outside the fireball turns into smoke.
+const SMOKE: f32 = 0.3;
+/// Speed debris leaves the blast with, as a share of its radius.
+const THROW: f32 = 0.5;
+
+/// A detonation: casts rays of length `radius` from the cell. Each cell a ray
+/// passes costs it one plus the cell's `hardness`, so hard materials shield
+/// what is behind them and the Dirt border of walled edges stops it.
+///
+/// Cells reached are heated, and those that change when heated (water into
+/// steam, more explosive) are left to `World::update_heat`. Anything else is
+/// destroyed: close to the centre it becomes fire, further out smoke or
+/// nothing, and some of it is thrown from the end of its ray as debris, to
+/// fly on if it is a powder.
+pub struct Explosive {
+    pub radius: f32,
+}
+
+impl Explosive {
+    fn ray(
+        &self,
+        world: &mut World,
+        (y, x): (usize, usize),
+        angle: f32,
+        hit: &mut HashSet<(usize, usize)>,
+    ) {
+        let (sin, cos) = angle.sin_cos();
+        let mut energy = self.radius;
+        let mut debris: Vec<Cell> = Vec::new();
+        let mut last_free = None;
+        let mut step = 1.0;
+
+        while energy > 0.0 {
+            let (dy, dx) = ((sin * step).round() as i32, (cos * step).round() as i32);
+            step += 1.0;
+
+            let (ny, nx) = match world.neighbour(y, x, dy, dx) {
+                Neighbour::Cell(ny, nx) if !world.is_border(ny, nx) => (ny, nx),
+                _ => break,
+            };
+
+            let cell = world.tmp[[ny, nx]];
+            let props = world.registry().get(cell.cell_type);
+            let (hardness, state, heated) = (props.hardness, props.state, props.heated.is_some());
+            energy -= 1.0 + hardness;
+            if energy < 0.0 {
+                break;
+            }
+
+            world.tmp[[ny, nx]].temperature = cell.temperature.max(BLAST_HEAT);
+            if !hit.insert((ny, nx)) || heated {
+                continue;
+            }
+
+            if cell.cell_type.ne(&CellType::NONE) && world.rng().gen::<f32>() < DEBRIS {
+                debris.push(match state {
+                    State::Powder | State::Liquid => cell,
+                    _ => Cell {
+                        cell_type: CellType::SAND,
+                        mass: 0.0,
+                        ..cell
+                    },
+                });
+            }
+
+            let distance = step - 1.0;
+            let (cell_type, mass) = if distance < self.radius / 2.0 {
+                (CellType::FIRE_NORMAL, 0.0)
+            } else if world.rng().gen::<f32>() < SMOKE {
+                (CellType::SMOKE, 1.0)
+            } else {
+                (CellType::NONE, 0.0)
+            };
+            world.tmp[[ny, nx]].cell_type = cell_type;
+            world.tmp[[ny, nx]].mass = mass;
+            world.tmp[[ny, nx]].velocity = (0.0, 0.0);
+            last_free = Some((ny, nx));
+        }
+
+        // Whatever was blown off flies on from the far end of the ray
+        if let Some((ly, lx)) = last_free {
+            if let Some(cell) = debris.pop() {
+                let speed = self.radius * THROW;
+                world.tmp[[ly, lx]] = Cell {
+                    y: ly,
+                    x: lx,
+                    velocity: (sin * speed, cos * speed),
+                    ..cell
+                };
+            }
+        }
+    }
+}
+
+impl Material for Explosive {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let mut hit = HashSet::new();
+        let rays = (TAU * self.radius * 2.0).ceil() as usize;
+
+        for i in 0..rays {
+            self.ray(world, (y, x), TAU * i as f32 / rays as f32, &mut hit);
+        }
+
+        world.tmp[[y, x]].cell_type = CellType::FIRE_NORMAL;
+        world.tmp[[y, x]].temperature = BLAST_HEAT;
+    }
+}
@@ -0,0 +1,68 @@
+use rand::Rng;
+
+use crate::world::{boundary::Neighbour, cell::CellType, material::Material, World, NEIGHBOURHOOD};
+
+/// Chance per tick that a burning cell puts out smoke above itself.
+const SMOKE: f32 = 0.2;
+
+/// Burns for as many ticks as its mass (the fuel it caught fire with) lasts,
+/// giving off its `heat_output` every tick and now and then some smoke. The
+/// heat is what sets flammable neighbours alight, by chance, see
+/// `World::update_heat`. Once out of fuel it leaves its `residue`, or smoke.
+///
+/// Flames without fuel fall through lighter fluids and burn out as soon as
+/// they land.
+pub struct Fire;
+
+impl Material for Fire {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let cell = world.data[[y, x]];
+
+        if cell.mass <= 0.0 {
+            let below = world.neighbour(y, x, 1, 0);
+            match below {
+                Neighbour::Cell(by, bx) if world.sinks_into(cell.cell_type, below) => {
+                    world.swap((y, x), (by, bx));
+                    world.tmp[[by, bx]].cell_type = CellType::FIRE_NORMAL;
+                    return;
+                }
+                Neighbour::Void => {
+                    world.vacate(y, x);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let props = world.registry().get(cell.cell_type);
+        let (heat, residue) = (props.heat_output, props.residue);
+        world.tmp[[y, x]].temperature += heat;
+        for step in NEIGHBOURHOOD {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, step.0, step.1) {
+                world.tmp[[ny, nx]].temperature += heat;
+            }
+        }
+
+        if cell.mass > 0.0 {
+            world.tmp[[y, x]].mass -= 1.0;
+
+            if let Neighbour::Cell(ay, ax) = world.neighbour(y, x, -1, 0) {
+                if world.tmp[[ay, ax]].cell_type.eq(&CellType::NONE)
+                    && world.rng().gen::<f32>() < SMOKE
+                {
+                    world.tmp[[ay, ax]].cell_type = CellType::SMOKE;
+                    world.tmp[[ay, ax]].mass = 1.0;
+                }
+            }
+            return;
+        }
+
+        let (cell_type, mass) = match residue {
+            Some(residue) => (residue, 0.0),
+            None if cell.cell_type.eq(&CellType::FIRE_BURN) => (CellType::DARK_SMOKE, 2.0),
+            None => (CellType::SMOKE, 1.0),
+        };
+        world.tmp[[y, x]].cell_type = cell_type;
+        world.tmp[[y, x]].mass = mass;
+    }
+}
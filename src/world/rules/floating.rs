@@ -0,0 +1,227 @@
+use std::collections::BTreeSet;
+
+use ndarray::Array2;
+
+use crate::world::{
+    boundary::Neighbour,
+    cell::{Cell, CellType},
+    material::{Material, State},
+    World, GRAVITY, MAX_MASS, MAX_SPEED, SMALL_NEIGHBOURHOOD,
+};
+
+/// How much more liquid mass has to press on one side of a body than on the
+/// other before it drifts.
+const PUSH: f32 = MAX_MASS / 2.0;
+
+/// Moves connected regions as rigid bodies: they fall, speeding up, through
+/// lighter fluids, bob up through heavier ones a cell per tick and drift when
+/// liquid presses harder on one side than on the other. A body only moves
+/// if all of it can, so it keeps its shape.
+pub struct Floating;
+
+/// Ordered, so that adding up masses over a body gives the same result on
+/// every run.
+type Body = BTreeSet<(usize, usize)>;
+
+/// Collects the cells connected to `(y, x)` that `joins` accepts.
+fn body(
+    world: &World,
+    (y, x): (usize, usize),
+    joins: impl Fn(CellType) -> bool,
+    visited: &mut Array2<bool>,
+) -> Body {
+    let mut body = Body::new();
+    let mut stack = vec![(y, x)];
+    visited[[y, x]] = true;
+
+    while let Some((cy, cx)) = stack.pop() {
+        body.insert((cy, cx));
+
+        for step in SMALL_NEIGHBOURHOOD {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(cy, cx, step.0, step.1) {
+                if !visited[[ny, nx]] && joins(world.data[[ny, nx]].cell_type) {
+                    visited[[ny, nx]] = true;
+                    stack.push((ny, nx));
+                }
+            }
+        }
+    }
+
+    body
+}
+
+/// Whether every cell just outside `body` in direction `(dy, dx)` passes
+/// `test`, given the body cell it borders.
+fn leading_edge(
+    world: &World,
+    body: &Body,
+    (dy, dx): (i32, i32),
+    test: impl Fn(CellType, Neighbour) -> bool,
+) -> bool {
+    body.iter()
+        .all(|&(y, x)| match world.neighbour(y, x, dy, dx) {
+            Neighbour::Cell(ny, nx) if body.contains(&(ny, nx)) => true,
+            neighbour => test(world.tmp[[y, x]].cell_type, neighbour),
+        })
+}
+
+/// Liquid mass pressing on the left side of `body` minus that on the right.
+fn pressure(world: &World, body: &Body) -> f32 {
+    let mut pressure = 0.0;
+
+    for &(y, x) in body {
+        for (dx, sign) in [(-1, 1.0), (1, -1.0)] {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, 0, dx) {
+                let cell = &world.tmp[[ny, nx]];
+                if !body.contains(&(ny, nx)) && world.state(cell.cell_type).eq(&State::Liquid) {
+                    pressure += sign * cell.mass;
+                }
+            }
+        }
+    }
+
+    pressure
+}
+
+/// Which way `body` moves this tick, if at all.
+fn direction(world: &World, body: &Body) -> Option<(i32, i32)> {
+    if leading_edge(world, body, (1, 0), |cell_type, below| {
+        world.sinks_into(cell_type, below)
+    }) {
+        return Some((1, 0));
+    }
+
+    if leading_edge(world, body, (-1, 0), |cell_type, above| {
+        matches!(above, Neighbour::Cell(..)) && world.rises_into(cell_type, above)
+    }) {
+        return Some((-1, 0));
+    }
+
+    let pressure = pressure(world, body);
+    let dx = if pressure > PUSH {
+        1
+    } else if pressure < -PUSH {
+        -1
+    } else {
+        return None;
+    };
+
+    let free = |_, side| match side {
+        Neighbour::Cell(y, x) => world.is_fluid(world.tmp[[y, x]].cell_type),
+        Neighbour::Wall => false,
+        Neighbour::Void => true,
+    };
+    leading_edge(world, body, (0, dx), free).then_some((0, dx))
+}
+
+/// Shifts `body` one cell along `(dy, dx)` in `tmp`. Whatever was in the way
+/// moves to the cell the body leaves behind in the same row or column.
+fn translate(world: &mut World, body: &Body, (dy, dx): (i32, i32)) {
+    let mut moves: Vec<((usize, usize), Cell)> = Vec::new();
+
+    for &(y, x) in body {
+        let content = world.tmp[[y, x]];
+        let displaced = match world.neighbour(y, x, dy, dx) {
+            Neighbour::Cell(ny, nx) if body.contains(&(ny, nx)) => {
+                moves.push(((ny, nx), content));
+                continue;
+            }
+            Neighbour::Cell(ny, nx) => {
+                moves.push(((ny, nx), content));
+                world.tmp[[ny, nx]]
+            }
+            Neighbour::Void => Cell::default(),
+            Neighbour::Wall => continue,
+        };
+
+        // Walk back to the trailing cell of this row or column
+        let mut trailing = (y, x);
+        while let Neighbour::Cell(py, px) = world.neighbour(trailing.0, trailing.1, -dy, -dx) {
+            if !body.contains(&(py, px)) {
+                break;
+            }
+            trailing = (py, px);
+        }
+        moves.push((trailing, displaced));
+    }
+
+    for ((y, x), cell) in moves {
+        world.tmp[[y, x]] = Cell { y, x, ..cell };
+    }
+}
+
+fn update_body(world: &mut World, body: &Body) {
+    let down = match direction(world, body) {
+        Some((1, 0)) => (1, 0),
+        Some(direction) => {
+            translate(world, body, direction);
+            return;
+        }
+        None => {
+            for &(y, x) in body {
+                world.tmp[[y, x]].velocity = (0.0, 0.0);
+            }
+            return;
+        }
+    };
+
+    // Falling bodies pick up speed, one cell at a time until they land
+    let speed = body
+        .iter()
+        .map(|&(y, x)| world.tmp[[y, x]].velocity.0)
+        .fold(0.0, f32::max);
+    let speed = (speed + GRAVITY).min(MAX_SPEED);
+    let mut body = body.clone();
+    let mut landed = false;
+    for step in 0..(speed.round() as usize).max(1) {
+        if step > 0 && direction(world, &body) != Some(down) {
+            landed = true;
+            break;
+        }
+
+        translate(world, &body, down);
+        body = body
+            .iter()
+            .filter_map(|&(y, x)| match world.neighbour(y, x, down.0, down.1) {
+                Neighbour::Cell(ny, nx) => Some((ny, nx)),
+                _ => None,
+            })
+            .collect();
+    }
+
+    for &(y, x) in &body {
+        world.tmp[[y, x]].velocity = (if landed { 0.0 } else { speed }, 0.0);
+    }
+}
+
+impl Material for Floating {
+    /// Moves the whole body of same-material cells containing `(y, x)`.
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let cell_type = world.data[[y, x]].cell_type;
+        let mut visited = Array2::from_elem(world.data.dim(), false);
+        let body = body(world, (y, x), |other| other.eq(&cell_type), &mut visited);
+        update_body(world, &body);
+    }
+
+    /// Moves every body once. Touching cells of any member material form one
+    /// body.
+    fn pass(&self, world: &mut World, members: &[bool]) {
+        let mut visited = Array2::from_elem(world.data.dim(), false);
+
+        for y in 0..world.height() {
+            for x in 0..world.width() {
+                if visited[[y, x]] || !members[world.data[[y, x]].cell_type.0 as usize] {
+                    continue;
+                }
+
+                let body = body(
+                    world,
+                    (y, x),
+                    |other| members[other.0 as usize],
+                    &mut visited,
+                );
+                update_body(world, &body);
+            }
+        }
+    }
+}
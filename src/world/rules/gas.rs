@@ -0,0 +1,30 @@
+use crate::world::{boundary::Neighbour, material::Material, World};
+
+/// How much mass a gas cell loses per tick; it vanishes at zero.
+const DECAY: f32 = 0.01;
+
+/// Rises through heavier fluids and fades away as its mass runs out.
+pub struct Gas;
+
+impl Material for Gas {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let above = world.neighbour(y, x, -1, 0);
+        let (ty, tx) = match above {
+            Neighbour::Cell(ay, ax) if world.rises_into(world.data[[y, x]].cell_type, above) => {
+                world.swap((y, x), (ay, ax));
+                (ay, ax)
+            }
+            Neighbour::Void => {
+                world.vacate(y, x);
+                return;
+            }
+            _ => (y, x),
+        };
+
+        world.tmp[[ty, tx]].mass = world.data[[y, x]].mass - DECAY;
+        world.tmp[[ty, tx]].cell_type = world.data[[y, x]].cell_type;
+        if world.tmp[[ty, tx]].mass <= 0.0 {
+            world.vacate(ty, tx);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::world::{
+    boundary::Neighbour,
+    material::{Material, State},
+    World, SMALL_NEIGHBOURHOOD,
+};
+
+use super::Liquid;
+
+/// Molten rock: flows like `liquid`, scorches flammable neighbours up to its
+/// own temperature and boils any other liquid it touches, solidifying into
+/// its `cooled` material as it does so. Away from liquids it only hardens
+/// once it cools down, see `World::update_heat`.
+pub struct Lava {
+    pub liquid: Liquid,
+}
+
+impl Material for Lava {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let cell = world.data[[y, x]];
+        let mut quenched = false;
+
+        for step in SMALL_NEIGHBOURHOOD {
+            let (ny, nx) = match world.neighbour(y, x, step.0, step.1) {
+                Neighbour::Cell(ny, nx) => (ny, nx),
+                _ => continue,
+            };
+
+            let other = world.tmp[[ny, nx]];
+            let props = world.registry().get(other.cell_type);
+            if props.ignition_point.is_some() {
+                world.tmp[[ny, nx]].temperature = other.temperature.max(cell.temperature);
+            } else if props.state.eq(&State::Liquid) && other.cell_type.ne(&cell.cell_type) {
+                if let Some(heated) = props.heated {
+                    world.tmp[[ny, nx]].cell_type = heated.into;
+                    world.tmp[[ny, nx]].temperature = other.temperature.max(heated.temperature);
+                    quenched = true;
+                }
+            }
+        }
+
+        if quenched {
+            if let Some(cooled) = world.registry().get(cell.cell_type).cooled {
+                world.tmp[[y, x]].cell_type = cooled.into;
+                world.tmp[[y, x]].temperature = cooled.temperature;
+                return;
+            }
+        }
+
+        self.liquid.update(world, y, x);
+    }
+}
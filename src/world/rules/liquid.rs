@@ -0,0 +1,245 @@
+use crate::world::{boundary::Neighbour, cell::CellType, material::Material, World, MAX_MASS};
+
+const MAX_COMPRESS: f32 = 0.3;
+const MIN_MASS: f32 = 0.01;
+const FLOW_SMOOTH: f32 = 0.75;
+
+/// Mass-based flow: every cell holds up to `MAX_MASS` (slightly more when
+/// compressed from above) and hands the excess down, sideways and, under
+/// pressure, up.
+///
+/// Different liquids do not mix: a liquid sinks through lighter fluids by
+/// trading places with them, so they settle in layers ordered by density. The
+/// default flow constants are those of water.
+pub struct Liquid {
+    /// Share of every flow held back each tick, from `0.0` (water) towards
+    /// `1.0` (barely moves).
+    pub viscosity: f32,
+    /// Flows above this are smoothed, and cells holding less stop spreading.
+    pub min_flow: f32,
+    /// Most mass that moves up or down between two cells per tick.
+    pub max_flow: f32,
+}
+
+impl Default for Liquid {
+    fn default() -> Self {
+        Self {
+            viscosity: 0.0,
+            min_flow: 0.5,
+            max_flow: 3.0,
+        }
+    }
+}
+
+impl Liquid {
+    fn slow(&self, flow: f32) -> f32 {
+        flow * (1.0 - self.viscosity)
+    }
+
+    /// Flows the liquid at `(y, x)` and returns how much mass left it.
+    pub(crate) fn spread(&self, world: &mut World, y: usize, x: usize) -> f32 {
+        let liquid = world.data[[y, x]].cell_type;
+        let mut flow;
+        let mut moved = 0.0;
+        let mut remaining_mass = world.data[[y, x]].mass;
+        if remaining_mass < MIN_MASS {
+            // Too little to flow, drop it but keep what flowed in this pass
+            world.tmp[[y, x]].mass -= remaining_mass;
+            world.tmp[[y, x]].sediment -= world.data[[y, x]].sediment;
+            if world.tmp[[y, x]].mass < MIN_MASS {
+                world.vacate(y, x);
+            }
+            return moved;
+        }
+
+        if self.settle(world, y, x) {
+            return 0.0;
+        }
+
+        // Down
+        let below = world.neighbour(y, x, 1, 0);
+        if let Some(dest) = dest_mass(world, below, liquid) {
+            flow = get_flow(world.data[[y, x]].mass, dest) - dest;
+            if flow > self.min_flow {
+                flow *= FLOW_SMOOTH;
+            }
+
+            flow = self
+                .slow(flow)
+                .clamp(0.0, self.max_flow.min(remaining_mass));
+
+            transfer(world, y, x, below, flow, liquid);
+            moved += flow;
+            remaining_mass -= flow;
+        }
+
+        if remaining_mass < self.min_flow {
+            return moved;
+        }
+
+        let below_full = match below {
+            Neighbour::Cell(by, bx) => {
+                world.data[[by, bx]].cell_type.ne(&liquid) || world.data[[by, bx]].mass >= MAX_MASS
+            }
+            _ => true,
+        };
+
+        if below_full {
+            // Right side
+            let right = world.neighbour(y, x, 0, 1);
+            if let Some(dest) = dest_mass(world, right, liquid) {
+                flow = (remaining_mass - dest) / 3.0;
+
+                if flow > self.min_flow {
+                    flow *= FLOW_SMOOTH;
+                }
+
+                flow = self.slow(flow).clamp(0.0, remaining_mass);
+
+                transfer(world, y, x, right, flow, liquid);
+                moved += flow;
+                remaining_mass -= flow;
+            }
+
+            if remaining_mass < self.min_flow {
+                return moved;
+            }
+
+            // Left side
+            let left = world.neighbour(y, x, 0, -1);
+            if let Some(dest) = dest_mass(world, left, liquid) {
+                flow = (remaining_mass - dest) / 3.0;
+                if flow > self.min_flow {
+                    flow *= FLOW_SMOOTH;
+                }
+
+                flow = self.slow(flow).clamp(0.0, remaining_mass);
+
+                transfer(world, y, x, left, flow, liquid);
+                moved += flow;
+                remaining_mass -= flow;
+            }
+        }
+
+        if remaining_mass < self.min_flow {
+            return moved;
+        }
+
+        // Pressure
+        let above = world.neighbour(y, x, -1, 0);
+        if let Some(dest) = dest_mass(world, above, liquid) {
+            if remaining_mass <= MAX_MASS + MAX_COMPRESS {
+                return moved;
+            }
+
+            flow = remaining_mass - get_flow(world.data[[y, x]].mass, dest);
+
+            if flow > self.min_flow {
+                flow *= 0.8;
+            }
+
+            flow = self
+                .slow(flow)
+                .clamp(0.0, self.max_flow.min(remaining_mass));
+
+            transfer(world, y, x, above, flow, liquid);
+            moved += flow;
+        }
+        moved
+    }
+
+    /// Trades places with a lighter fluid below or a heavier one above, so
+    /// liquids settle in layers and gas bubbles up through them. Flowing into
+    /// empty space is left to the mass transfer.
+    fn settle(&self, world: &mut World, y: usize, x: usize) -> bool {
+        let liquid = world.data[[y, x]].cell_type;
+
+        for dy in [1, -1] {
+            let target = world.neighbour(y, x, dy, 0);
+            if let Neighbour::Cell(ny, nx) = target {
+                let moves = if dy > 0 {
+                    world.sinks_into(liquid, target)
+                } else {
+                    world.rises_into(liquid, target)
+                };
+
+                if moves && world.tmp[[ny, nx]].cell_type.ne(&CellType::NONE) {
+                    world.swap((y, x), (ny, nx));
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+fn get_flow(mass: f32, dest_mass: f32) -> f32 {
+    let sum = mass + dest_mass;
+
+    if sum <= MAX_MASS {
+        MAX_MASS
+    } else if sum < 2.0 * MAX_MASS + MAX_COMPRESS {
+        (MAX_MASS.powi(2) + sum * MAX_COMPRESS) / (MAX_MASS + MAX_COMPRESS)
+    } else {
+        (sum + MAX_COMPRESS) / 2.0
+    }
+}
+
+/// Adds `mass` at `temperature` to a cell in `tmp`, mixing the temperatures.
+fn add_mass(world: &mut World, (y, x): (usize, usize), mass: f32, temperature: f32) {
+    let cell = &mut world.tmp[[y, x]];
+    let total = cell.mass + mass;
+    if total > 0.0 {
+        cell.temperature = (cell.temperature * cell.mass + temperature * mass) / total;
+    }
+    cell.mass = total;
+}
+
+/// The mass already at `target` if `liquid` may flow into it: empty space, the
+/// same liquid, or an open edge, which swallows whatever flows into it. Cells
+/// that something else moved into this pass are skipped.
+fn dest_mass(world: &World, target: Neighbour, liquid: CellType) -> Option<f32> {
+    match target {
+        Neighbour::Cell(ty, tx) => {
+            let cell = &world.data[[ty, tx]];
+            let now = world.tmp[[ty, tx]].cell_type;
+            let accepts =
+                |cell_type: CellType| cell_type.eq(&liquid) || cell_type.eq(&CellType::NONE);
+            if accepts(cell.cell_type) && accepts(now) {
+                Some(cell.mass)
+            } else {
+                None
+            }
+        }
+        Neighbour::Void => Some(0.0),
+        Neighbour::Wall => None,
+    }
+}
+
+/// Moves `flow` mass, and the matching share of its sediment, from `(y, x)`
+/// into `target` in `tmp`.
+fn transfer(world: &mut World, y: usize, x: usize, target: Neighbour, flow: f32, liquid: CellType) {
+    let source = world.data[[y, x]];
+    let sediment = source.sediment * flow / source.mass;
+    world.tmp[[y, x]].mass -= flow;
+    world.tmp[[y, x]].sediment -= sediment;
+
+    if let Neighbour::Cell(ty, tx) = target {
+        let cell = &mut world.tmp[[ty, tx]];
+        if cell.cell_type.eq(&CellType::NONE) && cell.mass <= 0.0 {
+            cell.sediment = 0.0;
+        }
+        cell.sediment += sediment;
+        add_mass(world, (ty, tx), flow, world.tmp[[y, x]].temperature);
+        if world.tmp[[ty, tx]].mass > MIN_MASS {
+            world.tmp[[ty, tx]].cell_type = liquid;
+        }
+    }
+}
+
+impl Material for Liquid {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        self.spread(world, y, x);
+    }
+}
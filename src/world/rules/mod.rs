@@ -0,0 +1,33 @@
+//! Built-in `Material` implementations.
+
+mod absorbent;
+mod acid;
+mod circuit;
+mod emitter;
+mod explosive;
+mod fire;
+mod floating;
+mod gas;
+mod lava;
+mod liquid;
+mod plant;
+mod powder;
+mod sludge;
+mod steam;
+mod water;
+
+pub use absorbent::Absorbent;
+pub use acid::Acid;
+pub use circuit::Circuit;
+pub use emitter::Emitter;
+pub use explosive::Explosive;
+pub use fire::Fire;
+pub use floating::Floating;
+pub use gas::Gas;
+pub use lava::Lava;
+pub use liquid::Liquid;
+pub use plant::Plant;
+pub use powder::Powder;
+pub use sludge::Sludge;
+pub use steam::Steam;
+pub use water::Water;
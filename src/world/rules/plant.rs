@@ -0,0 +1,179 @@
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour, cell::CellType, material::Material, World, SMALL_NEIGHBOURHOOD,
+};
+
+/// Water mass a plant cell draws from a neighbouring water cell per tick.
+const DRINK: f32 = 0.1;
+/// Most water a plant cell stores as sap.
+const MAX_SAP: f32 = 2.0;
+/// Sap used up to grow one new cell.
+const GROW_COST: f32 = 1.0;
+
+/// Seeds fall like a powder and sprout once they rest on Dirt next to water,
+/// or on Mud. Plant cells drink from water touching them or the soil they
+/// stand on, and from Mud they stand on, into their mass, share it with the
+/// rest of the plant, and spend it to grow upwards and, now and then,
+/// sideways.
+pub struct Plant {
+    /// Chance per tick that a cell with enough sap grows.
+    pub rate: f32,
+    /// Chance that growth goes sideways instead of up.
+    pub branching: f32,
+    /// Cells above the ground a plant may reach.
+    pub max_height: usize,
+}
+
+impl Default for Plant {
+    fn default() -> Self {
+        Self {
+            rate: 0.1,
+            branching: 0.2,
+            max_height: 20,
+        }
+    }
+}
+
+impl Plant {
+    fn update_seed(&self, world: &mut World, y: usize, x: usize) {
+        let below = world.neighbour(y, x, 1, 0);
+        if world.sinks_into(CellType::SEED, below) {
+            match below {
+                Neighbour::Cell(by, bx) => world.swap((y, x), (by, bx)),
+                _ => world.vacate(y, x),
+            }
+            return;
+        }
+
+        let on_soil = matches!(below, Neighbour::Cell(by, bx)
+            if is_soil(world.data[[by, bx]].cell_type));
+        if on_soil && !water(world, y, x).is_empty() {
+            world.tmp[[y, x]].cell_type = CellType::PLANT;
+        }
+    }
+
+    fn update_plant(&self, world: &mut World, y: usize, x: usize) {
+        drink(world, y, x);
+        share_sap(world, y, x);
+
+        if world.tmp[[y, x]].mass < GROW_COST || !world.rng().gen_bool(self.rate as f64) {
+            return;
+        }
+
+        let (dy, dx) = if world.rng().gen_bool(self.branching as f64) {
+            (0, if world.rng().gen_bool(0.5) { 1 } else { -1 })
+        } else {
+            (-1, 0)
+        };
+
+        if let Neighbour::Cell(ty, tx) = world.neighbour(y, x, dy, dx) {
+            if world.tmp[[ty, tx]].cell_type.eq(&CellType::NONE)
+                && height(world, ty, tx, self.max_height) < self.max_height
+            {
+                world.tmp[[ty, tx]].cell_type = CellType::PLANT;
+                world.tmp[[ty, tx]].mass = 0.0;
+                world.tmp[[y, x]].mass -= GROW_COST;
+            }
+        }
+    }
+}
+
+/// Dirt, or Dirt that has soaked up water.
+fn is_soil(cell_type: CellType) -> bool {
+    matches!(cell_type, CellType::DIRT | CellType::MUD)
+}
+
+/// Cells the roots at `(y, x)` draw water from: Water touching it or the
+/// soil it stands on, and that soil itself if it is Mud.
+fn water(world: &World, y: usize, x: usize) -> Vec<(usize, usize)> {
+    let mut roots = vec![(y, x)];
+    let mut water = Vec::new();
+    if let Neighbour::Cell(by, bx) = world.neighbour(y, x, 1, 0) {
+        let soil = world.tmp[[by, bx]].cell_type;
+        if is_soil(soil) {
+            roots.push((by, bx));
+        }
+        if soil.eq(&CellType::MUD) {
+            water.push((by, bx));
+        }
+    }
+
+    for (ry, rx) in roots {
+        for step in SMALL_NEIGHBOURHOOD {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(ry, rx, step.0, step.1) {
+                if world.tmp[[ny, nx]].cell_type.eq(&CellType::WATER) {
+                    water.push((ny, nx));
+                }
+            }
+        }
+    }
+    water
+}
+
+/// Draws water from the cells its roots reach into the plant cell's mass.
+fn drink(world: &mut World, y: usize, x: usize) {
+    for (wy, wx) in water(world, y, x) {
+        if world.tmp[[y, x]].mass >= MAX_SAP {
+            return;
+        }
+
+        let sip = DRINK.min(world.tmp[[wy, wx]].mass);
+        world.tmp[[wy, wx]].mass -= sip;
+        world.tmp[[y, x]].mass += sip;
+
+        // Mud holds its water in its mass, and dries back into Dirt
+        let source = &mut world.tmp[[wy, wx]];
+        if source.cell_type.eq(&CellType::MUD) && source.mass <= 0.0 {
+            source.cell_type = CellType::DIRT;
+            source.mass = 0.0;
+        }
+    }
+}
+
+/// Hands sap to neighbouring plant cells that hold less.
+fn share_sap(world: &mut World, y: usize, x: usize) {
+    let sap = world.data[[y, x]].mass;
+
+    for step in SMALL_NEIGHBOURHOOD {
+        if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, step.0, step.1) {
+            let other = world.data[[ny, nx]];
+            if other.cell_type.eq(&CellType::PLANT) && other.mass < sap {
+                let flow = (sap - other.mass) / 5.0;
+                world.tmp[[ny, nx]].mass += flow;
+                world.tmp[[y, x]].mass -= flow;
+            }
+        }
+    }
+}
+
+/// How far `(y, x)` is above the ground, counting plant and empty cells
+/// below it, up to `limit`.
+fn height(world: &World, y: usize, x: usize, limit: usize) -> usize {
+    let (mut y, mut x) = (y, x);
+
+    for height in 0..limit {
+        match world.neighbour(y, x, 1, 0) {
+            Neighbour::Cell(by, bx)
+                if matches!(
+                    world.tmp[[by, bx]].cell_type,
+                    CellType::PLANT | CellType::NONE
+                ) =>
+            {
+                (y, x) = (by, bx);
+            }
+            _ => return height,
+        }
+    }
+
+    limit
+}
+
+impl Material for Plant {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        match world.data[[y, x]].cell_type {
+            CellType::SEED => self.update_seed(world, y, x),
+            _ => self.update_plant(world, y, x),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use rand::Rng;
+
+use crate::world::{
+    boundary::Neighbour,
+    material::{Material, State},
+    World, GRAVITY, MAX_SPEED,
+};
+
+/// Share of the speed of a landing cell that turns into a sideways splash.
+const SPLASH: f32 = 0.5;
+/// Share of its sideways speed a cell keeps per tick on the ground.
+const FRICTION: f32 = 0.5;
+/// Slower cells come to rest.
+const MIN_SPEED: f32 = 0.5;
+
+/// Falls, picking up speed, or slides off diagonally, sinking through
+/// lighter fluids. Landing turns part of the fall into a sideways skid, so
+/// poured powder splashes out, and thrown powder flies until it hits
+/// something. Liquids slow it down to a cell per tick.
+pub struct Powder {
+    /// How many cells must be free below a diagonal before the powder slides
+    /// into it. Higher values hold steeper piles.
+    pub steepness: i32,
+}
+
+impl Default for Powder {
+    fn default() -> Self {
+        Self { steepness: 1 }
+    }
+}
+
+pub(super) fn move_to(world: &mut World, y: usize, x: usize, target: Neighbour) {
+    match target {
+        Neighbour::Cell(ty, tx) => world.swap((y, x), (ty, tx)),
+        Neighbour::Void => world.vacate(y, x),
+        Neighbour::Wall => {}
+    }
+}
+
+impl Powder {
+    /// Whether the powder may slide off towards `dx`.
+    fn can_slide(&self, world: &World, y: usize, x: usize, dx: i32) -> bool {
+        let cell_type = world.data[[y, x]].cell_type;
+
+        world.sinks_into(cell_type, world.neighbour(y, x, 0, dx))
+            && (1..=self.steepness)
+                .all(|dy| world.sinks_into(cell_type, world.neighbour(y, x, dy, dx)))
+    }
+}
+
+impl Powder {
+    /// Updates the velocity of the powder at `(y, x)` and moves it along it.
+    /// Returns `false` if it is at rest.
+    fn fly(&self, world: &mut World, y: usize, x: usize) -> bool {
+        let cell = world.data[[y, x]];
+        let mut velocity = cell.velocity;
+
+        let below = world.neighbour(y, x, 1, 0);
+        if world.sinks_into(cell.cell_type, below) {
+            velocity.0 = (velocity.0 + GRAVITY).min(MAX_SPEED);
+            if let Neighbour::Cell(by, bx) = below {
+                if world
+                    .state(world.tmp[[by, bx]].cell_type)
+                    .eq(&State::Liquid)
+                {
+                    velocity.0 = velocity.0.min(1.0);
+                }
+            }
+        } else if velocity.0 > 0.0 {
+            let side = if velocity.1 != 0.0 {
+                velocity.1.signum()
+            } else if world.rng().gen_bool(0.5) {
+                1.0
+            } else {
+                -1.0
+            };
+            velocity = (0.0, velocity.1 + side * velocity.0 * SPLASH);
+        } else if velocity.0 < 0.0 {
+            velocity.0 += GRAVITY;
+        } else {
+            velocity.1 *= FRICTION;
+        }
+
+        if velocity.0 == 0.0 && velocity.1.abs() < MIN_SPEED {
+            world.tmp[[y, x]].velocity = (0.0, 0.0);
+            return false;
+        }
+
+        match world.trace(y, x, cell.cell_type, velocity) {
+            Some(target) => {
+                move_to(world, y, x, target);
+                if let Neighbour::Cell(ty, tx) = target {
+                    world.tmp[[ty, tx]].velocity = velocity;
+                }
+                true
+            }
+            None => {
+                world.tmp[[y, x]].velocity = (0.0, 0.0);
+                false
+            }
+        }
+    }
+}
+
+impl Material for Powder {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        if self.fly(world, y, x) {
+            return;
+        }
+
+        // Check if the powder can move diagonally left/right
+        let left_diag = world.neighbour(y, x, 1, -1);
+        let right_diag = world.neighbour(y, x, 1, 1);
+        let can_left = self.can_slide(world, y, x, -1);
+        let can_right = self.can_slide(world, y, x, 1);
+
+        if can_left && can_right {
+            if world.rng().gen_bool(0.5) {
+                move_to(world, y, x, right_diag);
+            } else {
+                move_to(world, y, x, left_diag);
+            }
+        } else if can_left {
+            move_to(world, y, x, left_diag);
+        } else if can_right {
+            move_to(world, y, x, right_diag);
+        }
+    }
+}
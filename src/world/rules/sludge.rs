@@ -0,0 +1,33 @@
+use rand::Rng;
+
+use crate::world::{material::Material, World};
+
+use super::powder::move_to;
+
+/// Falls like a powder, but when it cannot fall it oozes a cell sideways
+/// with chance `fluidity` per tick, so it slowly spreads out flat.
+pub struct Sludge {
+    pub fluidity: f32,
+}
+
+impl Material for Sludge {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let cell_type = world.data[[y, x]].cell_type;
+
+        let below = world.neighbour(y, x, 1, 0);
+        if world.sinks_into(cell_type, below) {
+            move_to(world, y, x, below);
+            return;
+        }
+
+        if world.rng().gen::<f32>() >= self.fluidity {
+            return;
+        }
+
+        let dx = if world.rng().gen_bool(0.5) { 1 } else { -1 };
+        let side = world.neighbour(y, x, 0, dx);
+        if world.sinks_into(cell_type, side) {
+            move_to(world, y, x, side);
+        }
+    }
+}
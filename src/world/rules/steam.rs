@@ -0,0 +1,40 @@
+use rand::Rng;
+
+use crate::world::{boundary::Neighbour, cell::CellType, material::Material, World};
+
+/// Rises through heavier fluids like smoke, bubbling up through liquids.
+/// Unlike smoke it never fades: it carries the mass of the water it boiled
+/// from and condenses back into water when it hits a ceiling. Condensing as
+/// it cools is handled by `World::update_heat`.
+pub struct Steam;
+
+impl Material for Steam {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let cell_type = world.data[[y, x]].cell_type;
+
+        let above = world.neighbour(y, x, -1, 0);
+        match above {
+            Neighbour::Cell(ay, ax) if world.rises_into(cell_type, above) => {
+                world.swap((y, x), (ay, ax));
+                return;
+            }
+            Neighbour::Cell(ay, ax) if world.is_fluid(world.tmp[[ay, ax]].cell_type) => {}
+            Neighbour::Cell(..) | Neighbour::Wall => {
+                world.tmp[[y, x]].cell_type = CellType::WATER;
+                return;
+            }
+            Neighbour::Void => {
+                world.vacate(y, x);
+                return;
+            }
+        }
+
+        // Held down by other gas, drift sideways
+        let dx = if world.rng().gen_bool(0.5) { 1 } else { -1 };
+        if let Neighbour::Cell(sy, sx) = world.neighbour(y, x, 0, dx) {
+            if world.tmp[[sy, sx]].cell_type.eq(&CellType::NONE) {
+                world.swap((y, x), (sy, sx));
+            }
+        }
+    }
+}
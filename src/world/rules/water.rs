@@ -0,0 +1,109 @@
+use rand::Rng;
+
+use crate::world::{boundary::Neighbour, cell::CellType, material::Material, World};
+
+use super::Liquid;
+
+/// Sediment a cell can carry per unit of mass flowing out of it each tick.
+const CARRY: f32 = 2.0;
+/// Chance per tick that fast enough water wears away a fully erodible
+/// neighbour.
+const PICKUP: f32 = 0.5;
+
+/// Flows like `liquid` and moves sand around. Water flowing fast enough to
+/// carry another cell's worth of sediment picks up an erodible neighbour;
+/// slower water lets its excess sediment sink, and once a cell at the bottom
+/// has gathered a whole cell's worth it settles as Sand, pushing the water
+/// out of the way.
+pub struct Water {
+    pub liquid: Liquid,
+}
+
+impl Water {
+    fn erode(&self, world: &mut World, y: usize, x: usize) {
+        for (dy, dx) in [(1, 0), (0, -1), (0, 1)] {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, dy, dx) {
+                let cell_type = world.data[[ny, nx]].cell_type;
+                let erodibility = world.registry().get(cell_type).erodibility;
+                if erodibility <= 0.0
+                    || world.tmp[[ny, nx]].cell_type.ne(&cell_type)
+                    || world.is_border(ny, nx)
+                {
+                    continue;
+                }
+
+                if world.rng().gen::<f32>() < erodibility * PICKUP {
+                    // Water soaked up by wet sand or mud rejoins the stream
+                    let soaked = world.tmp[[ny, nx]].mass;
+                    world.vacate(ny, nx);
+                    world.tmp[[y, x]].mass += soaked;
+                    world.tmp[[y, x]].sediment += 1.0;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn deposit(&self, world: &mut World, y: usize, x: usize, excess: f32, liquid: CellType) {
+        // Sink through the water below first
+        if let Neighbour::Cell(by, bx) = world.neighbour(y, x, 1, 0) {
+            if world.tmp[[by, bx]].cell_type.eq(&liquid) {
+                world.tmp[[y, x]].sediment -= excess;
+                world.tmp[[by, bx]].sediment += excess;
+                return;
+            }
+        }
+
+        let cell = world.tmp[[y, x]];
+        if cell.sediment < 1.0 {
+            return;
+        }
+
+        for (dy, dx) in [(-1, 0), (0, -1), (0, 1)] {
+            if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, dy, dx) {
+                let other = &mut world.tmp[[ny, nx]];
+                if other.cell_type.ne(&liquid) && other.cell_type.ne(&CellType::NONE) {
+                    continue;
+                }
+                if other.cell_type.eq(&CellType::NONE) {
+                    other.mass = 0.0;
+                    other.sediment = 0.0;
+                    other.temperature = cell.temperature;
+                }
+
+                let total = other.mass + cell.mass;
+                other.temperature =
+                    (other.temperature * other.mass + cell.temperature * cell.mass) / total;
+                other.mass = total;
+                other.sediment += cell.sediment - 1.0;
+                other.cell_type = liquid;
+
+                let sand = &mut world.tmp[[y, x]];
+                sand.cell_type = CellType::SAND;
+                sand.mass = 0.0;
+                sand.sediment = 0.0;
+                return;
+            }
+        }
+    }
+}
+
+impl Material for Water {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        let liquid = world.data[[y, x]].cell_type;
+        let flow = self.liquid.spread(world, y, x);
+
+        // Moved or dried up
+        if world.tmp[[y, x]].cell_type.ne(&liquid) {
+            return;
+        }
+
+        let capacity = flow * CARRY;
+        let sediment = world.tmp[[y, x]].sediment;
+        if sediment + 1.0 <= capacity {
+            self.erode(world, y, x);
+        } else if sediment > capacity {
+            self.deposit(world, y, x, sediment - capacity, liquid);
+        }
+    }
+}
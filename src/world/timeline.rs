@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+
+use super::{cell::Cell, World};
+
+/// A copy of everything `World::step` depends on, so that restoring it and
+/// stepping again reproduces the same history.
+#[derive(Clone)]
+pub struct Snapshot {
+    pub(super) data: Array2<Cell>,
+    pub(super) rng: StdRng,
+}
+
+/// Ring buffer of the most recent world states.
+///
+/// `record` appends after the current cursor; recording while rewound drops
+/// the states that were ahead of it, like an undo stack.
+pub struct Timeline {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+    cursor: usize,
+}
+
+impl Timeline {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            cursor: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Index of the snapshot the world was last restored from or recorded to.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.cursor = 0;
+    }
+
+    pub fn record(&mut self, world: &World) {
+        if !self.snapshots.is_empty() {
+            self.snapshots.truncate(self.cursor + 1);
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        self.snapshots.push_back(world.snapshot());
+        self.cursor = self.snapshots.len() - 1;
+    }
+
+    /// Restores the snapshot at `index` into `world`. Returns `false` if
+    /// there is no such snapshot.
+    pub fn seek(&mut self, index: usize, world: &mut World) -> bool {
+        match self.snapshots.get(index) {
+            Some(snapshot) => {
+                world.restore(snapshot);
+                self.cursor = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn back(&mut self, world: &mut World) -> bool {
+        self.cursor > 0 && self.seek(self.cursor - 1, world)
+    }
+
+    pub fn forward(&mut self, world: &mut World) -> bool {
+        self.seek(self.cursor + 1, world)
+    }
+}
@@ -0,0 +1,175 @@
+//! The update passes must cope with material on the outermost cells, with or
+//! without a Dirt border, for every boundary mode, and each mode has to do to
+//! material crossing the edge what it promises.
+
+use vaja_2::world::{
+    boundary::{Boundaries, Boundary},
+    cell::CellType,
+    World, MAX_MASS,
+};
+
+const STEPS: usize = 50;
+
+fn materials(world: &World) -> Vec<CellType> {
+    world
+        .registry()
+        .iter()
+        .map(|(cell_type, _)| cell_type)
+        .collect()
+}
+
+fn fill(world: &mut World, cell_type: CellType) {
+    for cell in &mut world.data {
+        cell.cell_type = cell_type;
+        cell.mass = MAX_MASS;
+    }
+}
+
+fn run(world: &mut World) {
+    for _ in 0..STEPS {
+        world.step();
+    }
+}
+
+#[test]
+fn every_material_fills_the_grid() {
+    for boundary in Boundary::ALL {
+        let mut world = World::with_seed(12, 9, 7);
+        world.boundaries = Boundaries::all(boundary);
+
+        for cell_type in materials(&world) {
+            fill(&mut world, cell_type);
+            run(&mut world);
+        }
+    }
+}
+
+#[test]
+fn mixed_materials_with_mixed_boundaries() {
+    for top in Boundary::ALL {
+        for bottom in Boundary::ALL {
+            for (left, right) in [
+                (Boundary::Wall, Boundary::Open),
+                (Boundary::Open, Boundary::Wrap),
+                (Boundary::Wrap, Boundary::Wall),
+            ] {
+                let mut world = World::with_seed(11, 8, 3);
+                world.boundaries = Boundaries {
+                    top,
+                    bottom,
+                    left,
+                    right,
+                };
+
+                let materials = materials(&world);
+                let width = world.width();
+                for ((y, x), cell) in world.data.indexed_iter_mut() {
+                    cell.cell_type = materials[(y * width + x * 3) % materials.len()];
+                    cell.mass = MAX_MASS;
+                }
+
+                run(&mut world);
+            }
+        }
+    }
+}
+
+#[test]
+fn border_removed_after_generation() {
+    let mut world = World::with_seed(20, 15, 11);
+    world.rand_generate();
+    world.smooth();
+
+    let (limit_y, limit_x) = (world.height() - 1, world.width() - 1);
+    let materials = materials(&world);
+    for (index, ((y, x), cell)) in world.data.indexed_iter_mut().enumerate() {
+        if y == 0 || x == 0 || y == limit_y || x == limit_x {
+            cell.cell_type = materials[index % materials.len()];
+            cell.mass = MAX_MASS;
+        }
+    }
+
+    run(&mut world);
+}
+
+#[test]
+fn tiny_worlds() {
+    for (width, height) in [(1, 1), (1, 4), (4, 1), (2, 2)] {
+        for boundary in Boundary::ALL {
+            let mut world = World::with_seed(width, height, 5);
+            world.boundaries = Boundaries::all(boundary);
+
+            for cell_type in materials(&world) {
+                fill(&mut world, cell_type);
+                world.smooth();
+                run(&mut world);
+            }
+        }
+    }
+}
+
+#[test]
+fn painting_reaches_the_edges() {
+    let mut world = World::with_seed(6, 4, 1);
+    world.transform_cell(0, 0, CellType::SAND);
+    world.transform_cell(3, 5, CellType::WATER);
+    world.transform_cell(4, 0, CellType::SAND);
+    world.transform_cell(-1, 2, CellType::SAND);
+
+    assert_eq!(world.data[[0, 0]].cell_type, CellType::SAND);
+    assert_eq!(world.data[[3, 5]].cell_type, CellType::WATER);
+    assert_eq!(
+        world
+            .data
+            .iter()
+            .filter(|cell| cell.cell_type != CellType::NONE)
+            .count(),
+        2
+    );
+}
+
+/// Drops a grain of sand down a column with `bottom` below it and returns
+/// where it is after every step.
+fn drop_sand(bottom: Boundary) -> Vec<Vec<(usize, usize)>> {
+    let mut world = World::with_seed(5, 8, 1);
+    world.boundaries = Boundaries::all(Boundary::Wall);
+    world.boundaries.bottom = bottom;
+    world.transform_cell(0, 2, CellType::SAND);
+
+    (0..20)
+        .map(|_| {
+            world.step();
+            world
+                .data
+                .indexed_iter()
+                .filter(|(_, cell)| cell.cell_type == CellType::SAND)
+                .map(|(position, _)| position)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn sand_falls_out_of_an_open_bottom() {
+    let path = drop_sand(Boundary::Open);
+    assert_eq!(path[0], [(1, 2)]);
+    assert!(path.last().unwrap().is_empty());
+}
+
+#[test]
+fn sand_wraps_through_the_bottom_to_the_top() {
+    let path = drop_sand(Boundary::Wrap);
+    assert!(path.iter().all(|sand| sand.len() == 1));
+    // Having reached the bottom row it comes back in from the top
+    let bottom = path.iter().position(|sand| sand[0].0 == 7).unwrap();
+    assert!(path[bottom + 1][0].0 < 3);
+}
+
+#[test]
+fn sand_lands_on_a_wall() {
+    let path = drop_sand(Boundary::Wall);
+    let resting = path.last().unwrap();
+    assert_eq!(resting.len(), 1);
+    assert_eq!(resting[0].0, 7);
+    assert_eq!(path[path.len() - 2], *resting);
+}
@@ -0,0 +1,504 @@
+//! Behaviour of the built-in material rules, checked on small scenes.
+
+use vaja_2::world::{cell::CellType, World};
+
+fn fill(
+    world: &mut World,
+    rows: std::ops::Range<i32>,
+    cols: std::ops::Range<i32>,
+    cell_type: CellType,
+) {
+    for y in rows {
+        for x in cols.clone() {
+            world.transform_cell(y, x, cell_type);
+        }
+    }
+}
+
+fn mass_of(world: &World, kinds: &[CellType]) -> f32 {
+    world
+        .data
+        .iter()
+        .filter(|cell| kinds.contains(&cell.cell_type))
+        .map(|cell| cell.mass)
+        .sum()
+}
+
+fn cells(world: &World, cell_type: CellType) -> Vec<(usize, usize)> {
+    world
+        .data
+        .indexed_iter()
+        .filter(|(_, cell)| cell.cell_type.eq(&cell_type))
+        .map(|(position, _)| position)
+        .collect()
+}
+
+/// A sealed box with a pool of water at the bottom.
+fn basin() -> World {
+    let mut world = World::with_seed(12, 12, 5);
+    world.add_border();
+    fill(&mut world, 7..11, 1..11, CellType::WATER);
+    world
+}
+
+#[test]
+fn water_washes_sand_away() {
+    let mut world = World::with_seed(30, 16, 3);
+    world.add_border();
+    fill(&mut world, 11..15, 1..29, CellType::SAND);
+    for _ in 0..5 {
+        fill(&mut world, 2..8, 2..10, CellType::WATER);
+    }
+
+    let mut carried: f32 = 0.0;
+    for _ in 0..300 {
+        world.step();
+        let sediment = world.data.iter().map(|cell| cell.sediment).sum();
+        carried = carried.max(sediment);
+    }
+
+    assert!(carried > 0.0);
+}
+
+#[test]
+fn clearing_forgets_sediment_and_speed() {
+    let mut world = World::with_seed(8, 8, 1);
+    world.data[[3, 3]].sediment = 0.8;
+    world.data[[3, 3]].velocity = (3.0, 0.0);
+    world.clear();
+    world.transform_cell(3, 3, CellType::WATER);
+
+    assert_eq!(world.data[[3, 3]].sediment, 0.0);
+    assert_eq!(world.data[[3, 3]].velocity, (0.0, 0.0));
+}
+
+#[test]
+fn boiling_and_condensing_keeps_the_water() {
+    let mut world = basin();
+    for cell in world.data.iter_mut() {
+        cell.temperature = 150.0;
+    }
+    let kinds = [CellType::WATER, CellType::STEAM];
+    let water = mass_of(&world, &kinds);
+
+    let mut boiled: f32 = 0.0;
+    for _ in 0..30 {
+        world.step();
+        boiled = boiled.max(mass_of(&world, &[CellType::STEAM]));
+        assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+    }
+    assert!(boiled > 0.0);
+
+    world.ambient_temperature = 20.0;
+    for _ in 0..300 {
+        world.step();
+        assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+    }
+    assert_eq!(mass_of(&world, &[CellType::STEAM]), 0.0);
+}
+
+#[test]
+fn freezing_and_melting_keeps_the_water() {
+    let mut world = basin();
+    let kinds = [CellType::WATER, CellType::ICE];
+    let water = mass_of(&world, &kinds);
+
+    world.ambient_temperature = -30.0;
+    for _ in 0..400 {
+        world.step();
+        assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+    }
+    assert_eq!(mass_of(&world, &[CellType::WATER]), 0.0);
+
+    world.ambient_temperature = 30.0;
+    for _ in 0..400 {
+        world.step();
+        assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+    }
+    assert_eq!(mass_of(&world, &[CellType::ICE]), 0.0);
+}
+
+#[test]
+fn seeds_grow_on_mud() {
+    let mut world = World::with_seed(12, 20, 1);
+    world.add_border();
+    fill(&mut world, 15..19, 1..11, CellType::MUD);
+    world.transform_cell(14, 5, CellType::SEED);
+    for _ in 0..300 {
+        world.step();
+    }
+
+    let plant = world
+        .data
+        .iter()
+        .filter(|cell| cell.cell_type.eq(&CellType::PLANT))
+        .count();
+    assert!(plant > 1);
+}
+
+#[test]
+fn heat_spreads_and_fades_to_ambient() {
+    let mut world = World::with_seed(9, 9, 1);
+    fill(&mut world, 0..9, 0..9, CellType::STONE);
+    world.data[[4, 4]].temperature = 500.0;
+
+    world.step();
+    assert!(world.data[[4, 5]].temperature > world.ambient_temperature);
+    assert!(world.data[[4, 4]].temperature < 500.0);
+
+    for _ in 0..2000 {
+        world.step();
+    }
+    let ambient = world.ambient_temperature;
+    assert!(world
+        .data
+        .iter()
+        .all(|cell| (cell.temperature - ambient).abs() < 1.0));
+}
+
+#[test]
+fn lava_quenched_by_water_turns_to_stone_and_steam() {
+    let mut world = basin();
+    fill(&mut world, 2..4, 4..8, CellType::LAVA);
+
+    let mut steam = 0;
+    for _ in 0..60 {
+        world.step();
+        steam = steam.max(cells(&world, CellType::STEAM).len());
+    }
+
+    assert!(steam > 0);
+    assert!(!cells(&world, CellType::STONE).is_empty());
+    assert!(cells(&world, CellType::LAVA).is_empty());
+}
+
+#[test]
+fn oil_floats_on_water() {
+    let mut world = World::with_seed(12, 16, 1);
+    world.add_border();
+    for _ in 0..3 {
+        fill(&mut world, 12..15, 1..11, CellType::OIL);
+        fill(&mut world, 5..8, 1..11, CellType::WATER);
+    }
+    for _ in 0..300 {
+        world.step();
+    }
+
+    let oil = cells(&world, CellType::OIL);
+    let water = cells(&world, CellType::WATER);
+    let lowest_oil = oil.iter().map(|&(y, _)| y).max().unwrap();
+    let highest_water = water.iter().map(|&(y, _)| y).min().unwrap();
+    assert!(lowest_oil < highest_water);
+}
+
+#[test]
+fn sand_sinks_below_water() {
+    let mut world = World::with_seed(12, 16, 1);
+    world.add_border();
+    for _ in 0..3 {
+        fill(&mut world, 10..15, 1..11, CellType::WATER);
+    }
+    fill(&mut world, 2..4, 1..11, CellType::SAND);
+    for _ in 0..200 {
+        world.step();
+    }
+
+    let mut sand = cells(&world, CellType::SAND);
+    sand.extend(cells(&world, CellType::WET_SAND));
+    let water = cells(&world, CellType::WATER);
+    let highest_sand = sand.iter().map(|&(y, _)| y).min().unwrap();
+    let lowest_water = water.iter().map(|&(y, _)| y).max().unwrap();
+    assert_eq!(sand.len(), 20);
+    assert!(lowest_water < highest_sand);
+}
+
+#[test]
+fn raft_keeps_its_shape() {
+    let shape = |world: &World| {
+        let wood = cells(world, CellType::WOOD);
+        let top = wood.iter().map(|&(y, _)| y).min().unwrap();
+        let left = wood.iter().map(|&(_, x)| x).min().unwrap();
+        let mut shape: Vec<_> = wood.iter().map(|&(y, x)| (y - top, x - left)).collect();
+        shape.sort_unstable();
+        (top, shape)
+    };
+
+    let mut world = World::with_seed(16, 16, 1);
+    world.add_border();
+    for _ in 0..3 {
+        fill(&mut world, 9..15, 1..15, CellType::WATER);
+    }
+    fill(&mut world, 2..4, 4..9, CellType::WOOD);
+    world.transform_cell(1, 6, CellType::WOOD);
+    let (start, raft) = shape(&world);
+
+    for _ in 0..100 {
+        world.step();
+        assert_eq!(shape(&world).1, raft);
+    }
+    assert!(shape(&world).0 > start);
+}
+
+/// Pours acid on a bed of `bed` in a walled box. Returns how many of the
+/// bed's cells are dissolved within `steps` ticks and the acid mass used.
+fn acid_on(bed: CellType, steps: usize) -> (usize, f32) {
+    let mut world = World::with_seed(12, 12, 1);
+    world.add_border();
+    fill(&mut world, 7..11, 1..11, bed);
+    for _ in 0..3 {
+        fill(&mut world, 4..6, 3..8, CellType::ACID);
+    }
+    let (before, acid) = (cells(&world, bed).len(), mass_of(&world, &[CellType::ACID]));
+    for _ in 0..steps {
+        world.step();
+    }
+
+    (
+        before - cells(&world, bed).len(),
+        acid - mass_of(&world, &[CellType::ACID]),
+    )
+}
+
+#[test]
+fn acid_eats_through_dirt() {
+    let (dissolved, used) = acid_on(CellType::DIRT, 100);
+    assert!(dissolved > 0);
+    assert!((used - dissolved as f32).abs() < 1e-3);
+}
+
+#[test]
+fn acid_eats_less_resistant_materials_faster() {
+    assert!(acid_on(CellType::ASH, 50).0 > acid_on(CellType::DIRT, 50).0);
+}
+
+#[test]
+fn acid_is_used_up_by_what_it_dissolves() {
+    let mut world = World::with_seed(12, 12, 1);
+    world.add_border();
+    fill(&mut world, 3..11, 1..11, CellType::DIRT);
+    // A pit holding 9 mass of acid
+    fill(&mut world, 3..6, 5..6, CellType::NONE);
+    for _ in 0..5 {
+        world.transform_cell(5, 5, CellType::ACID);
+    }
+    let dirt = cells(&world, CellType::DIRT).len();
+    for _ in 0..300 {
+        world.step();
+    }
+
+    let dissolved = dirt - cells(&world, CellType::DIRT).len();
+    assert!((1..=9).contains(&dissolved));
+}
+
+#[test]
+fn acid_leaves_the_walls_alone() {
+    let mut world = World::with_seed(12, 12, 1);
+    world.add_border();
+    let border = cells(&world, CellType::DIRT);
+    for _ in 0..10 {
+        fill(&mut world, 1..11, 1..11, CellType::ACID);
+    }
+    for _ in 0..300 {
+        world.step();
+    }
+
+    assert_eq!(cells(&world, CellType::DIRT), border);
+}
+
+#[test]
+fn seeds_grow_next_to_water() {
+    let mut world = World::with_seed(12, 20, 1);
+    world.add_border();
+    fill(&mut world, 15..19, 1..11, CellType::DIRT);
+    for _ in 0..5 {
+        fill(&mut world, 14..15, 6..11, CellType::WATER);
+    }
+    world.transform_cell(10, 4, CellType::SEED);
+    for _ in 0..300 {
+        world.step();
+    }
+
+    assert!(cells(&world, CellType::SEED).is_empty());
+    assert!(cells(&world, CellType::PLANT).len() > 1);
+}
+
+/// How far along a wire with `switch` halfway down an electron gets.
+fn furthest_electron(switch: CellType) -> usize {
+    let mut world = World::with_seed(20, 5, 1);
+    fill(&mut world, 2..3, 1..19, CellType::WIRE);
+    world.transform_cell(2, 10, switch);
+    world.transform_cell(2, 2, CellType::ELECTRON_HEAD);
+
+    let mut furthest = 0;
+    for _ in 0..40 {
+        world.step();
+        for (_, x) in cells(&world, CellType::ELECTRON_HEAD) {
+            furthest = furthest.max(x);
+        }
+    }
+    furthest
+}
+
+#[test]
+fn switches_pass_or_stop_electrons() {
+    assert!(furthest_electron(CellType::SWITCH_OFF) < 10);
+    assert_eq!(furthest_electron(CellType::SWITCH_ON), 18);
+}
+
+/// Sets off gunpowder at `(y, x)`.
+fn detonate(world: &mut World, y: i32, x: i32) {
+    world.transform_cell(y, x, CellType::GUNPOWDER);
+    world.data[[y as usize, x as usize]].temperature = 200.0;
+    for _ in 0..5 {
+        world.step();
+    }
+}
+
+#[test]
+fn hard_materials_withstand_blasts() {
+    // Dirt and stone as far from the charge, above and below it in the air
+    let mut world = World::with_seed(21, 21, 1);
+    fill(&mut world, 7..8, 8..13, CellType::DIRT);
+    fill(&mut world, 13..14, 8..13, CellType::STONE);
+    detonate(&mut world, 10, 10);
+
+    assert!(cells(&world, CellType::DIRT).len() < 5);
+    assert_eq!(cells(&world, CellType::STONE).len(), 5);
+}
+
+#[test]
+fn blasts_leave_the_walls_alone() {
+    let mut world = World::with_seed(21, 21, 1);
+    world.add_border();
+    let border = cells(&world, CellType::DIRT);
+    detonate(&mut world, 1, 10);
+
+    assert_eq!(cells(&world, CellType::DIRT), border);
+}
+
+#[test]
+fn emitters_fill_and_sinks_drain() {
+    let mut world = World::with_seed(10, 10, 1);
+    world.add_border();
+    world.transform_cell(1, 5, CellType::EMITTER);
+    for _ in 0..20 {
+        world.step();
+    }
+    assert!(mass_of(&world, &[CellType::WATER]) > 0.0);
+
+    let mut world = World::with_seed(10, 10, 1);
+    world.add_border();
+    world.transform_cell(8, 5, CellType::SINK);
+    for _ in 0..3 {
+        fill(&mut world, 6..8, 1..9, CellType::WATER);
+    }
+    let water = mass_of(&world, &[CellType::WATER]);
+    for _ in 0..200 {
+        world.step();
+    }
+    assert!(mass_of(&world, &[CellType::WATER]) < water / 2.0);
+}
+
+#[test]
+fn still_water_drops_its_sediment_as_sand() {
+    let mut world = basin();
+    for cell in world.data.iter_mut() {
+        if cell.cell_type.eq(&CellType::WATER) {
+            cell.sediment = 1.5;
+        }
+    }
+    let sediment = |world: &World| world.data.iter().map(|cell| cell.sediment).sum::<f32>();
+    let carried = sediment(&world);
+    for _ in 0..100 {
+        world.step();
+    }
+
+    let sand = cells(&world, CellType::SAND).len() + cells(&world, CellType::WET_SAND).len();
+    assert!(sand > 0);
+    assert!(sediment(&world) < carried);
+}
+
+#[test]
+fn sand_soaking_up_water_keeps_it() {
+    let mut world = World::with_seed(14, 14, 2);
+    world.add_border();
+    fill(&mut world, 9..13, 1..13, CellType::SAND);
+    for _ in 0..3 {
+        fill(&mut world, 6..8, 1..13, CellType::WATER);
+    }
+    // Dry sand holds no mass, wet sand holds the water it soaked up
+    let kinds = [
+        CellType::WATER,
+        CellType::STEAM,
+        CellType::SAND,
+        CellType::WET_SAND,
+    ];
+    let water = mass_of(&world, &kinds);
+
+    for _ in 0..300 {
+        world.step();
+        assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+    }
+    assert!(!cells(&world, CellType::WET_SAND).is_empty());
+}
+
+#[test]
+fn wood_burns_for_a_while_and_leaves_charcoal() {
+    let mut world = World::with_seed(10, 10, 1);
+    world.add_border();
+    world.transform_cell(8, 5, CellType::WOOD);
+    world.data[[8, 5]].temperature = 1000.0;
+
+    let mut burning = 0;
+    let mut charred = false;
+    for _ in 0..400 {
+        world.step();
+        match world.data[[8, 5]].cell_type {
+            CellType::FIRE_BURN | CellType::EMBERS => burning += 1,
+            CellType::CHARCOAL => charred = true,
+            _ => {}
+        }
+    }
+
+    assert!(burning > 10);
+    assert!(charred);
+    assert!(world.data[[8, 5]].cell_type.eq(&CellType::ASH));
+}
+
+#[test]
+fn falling_sand_speeds_up() {
+    let mut world = World::with_seed(5, 60, 1);
+    world.add_border();
+    world.transform_cell(1, 2, CellType::SAND);
+
+    let mut ticks = 0;
+    while world.data[[58, 2]].cell_type.ne(&CellType::SAND) {
+        world.step();
+        ticks += 1;
+        // 57 cells down, at a cell per tick it would take 57 ticks
+        assert!(ticks < 30);
+    }
+}
+
+#[test]
+fn acid_frees_the_water_in_wet_sand() {
+    let mut world = World::with_seed(12, 12, 1);
+    world.add_border();
+    fill(&mut world, 7..11, 1..11, CellType::WET_SAND);
+    let kinds = [
+        CellType::WATER,
+        CellType::STEAM,
+        CellType::SAND,
+        CellType::WET_SAND,
+    ];
+    let water = mass_of(&world, &kinds);
+    for _ in 0..3 {
+        fill(&mut world, 4..6, 3..8, CellType::ACID);
+    }
+    for _ in 0..100 {
+        world.step();
+    }
+
+    assert!(cells(&world, CellType::WET_SAND).len() < 40);
+    assert!((mass_of(&world, &kinds) - water).abs() < 1e-3);
+}
@@ -0,0 +1,115 @@
+//! Materials defined outside the crate, or built-in ones set up differently,
+//! plugged in through a `Registry`.
+
+use std::sync::Arc;
+
+use vaja_2::world::{
+    boundary::Neighbour,
+    cell::CellType,
+    material::{Material, MaterialProps, Registry, State},
+    rules::Plant,
+    World,
+};
+
+/// Crawls a cell to the right every tick while there is room.
+struct Crawler;
+
+impl Material for Crawler {
+    fn update(&self, world: &mut World, y: usize, x: usize) {
+        if let Neighbour::Cell(ny, nx) = world.neighbour(y, x, 0, 1) {
+            if world.tmp[[ny, nx]].cell_type.eq(&CellType::NONE) {
+                world.swap((y, x), (ny, nx));
+            }
+        }
+    }
+}
+
+fn world_with_crawler() -> (World, CellType) {
+    let mut registry = Registry::default();
+    let crawler = registry.register(MaterialProps {
+        name: "Crawler",
+        color: [255, 0, 0],
+        density: 2.0,
+        state: State::Solid,
+        rule: Some(Arc::new(Crawler)),
+        paintable: true,
+        ..Default::default()
+    });
+
+    (World::with_registry(10, 6, 1, registry), crawler)
+}
+
+#[test]
+fn custom_rules_run() {
+    let (mut world, crawler) = world_with_crawler();
+    assert_eq!(world.registry().find("Crawler"), Some(crawler));
+
+    world.transform_cell(2, 1, crawler);
+    for _ in 0..5 {
+        world.step();
+    }
+    assert_eq!(world.data[[2, 6]].cell_type, crawler);
+    assert_eq!(world.data[[2, 1]].cell_type, CellType::NONE);
+
+    for _ in 0..5 {
+        world.step();
+    }
+    assert_eq!(world.data[[2, 9]].cell_type, crawler);
+}
+
+#[test]
+fn built_in_materials_work_next_to_custom_ones() {
+    let (mut world, crawler) = world_with_crawler();
+    world.transform_cell(0, 3, CellType::SAND);
+    world.transform_cell(2, 5, crawler);
+    for _ in 0..10 {
+        world.step();
+    }
+
+    assert!((0..10).any(|x| world.data[[5, x]].cell_type == CellType::SAND));
+    assert_eq!(world.data[[2, 9]].cell_type, crawler);
+}
+
+/// Grows a seed on a bed of mud with the built-in plant rule swapped for
+/// `plant`, and returns the height of the plant.
+fn grow(plant: Plant) -> usize {
+    let mut registry = Registry::default();
+    let plant: Arc<dyn Material> = Arc::new(plant);
+    registry.get_mut(CellType::SEED).rule = Some(plant.clone());
+    registry.get_mut(CellType::PLANT).rule = Some(plant);
+
+    let mut world = World::with_registry(12, 20, 1, registry);
+    world.add_border();
+    for y in 15..19 {
+        for x in 1..11 {
+            world.transform_cell(y, x, CellType::MUD);
+        }
+    }
+    world.transform_cell(14, 5, CellType::SEED);
+    for _ in 0..1500 {
+        world.step();
+    }
+
+    let top = world
+        .data
+        .indexed_iter()
+        .filter(|(_, cell)| cell.cell_type == CellType::PLANT)
+        .map(|((y, _), _)| y)
+        .min()
+        .unwrap();
+    15 - top
+}
+
+#[test]
+fn built_in_rules_can_be_reconfigured() {
+    // Growing straight up, so nothing overhangs the puddles the mud leaves
+    let stalk = |max_height| Plant {
+        max_height,
+        branching: 0.0,
+        ..Default::default()
+    };
+
+    assert_eq!(grow(stalk(3)), 3);
+    assert_eq!(grow(stalk(5)), 5);
+    assert!(grow(stalk(20)) > 5);
+}